@@ -0,0 +1,380 @@
+//! Build, point update and fold of every `structure::ranged` type at N = 1e5 and 1e6.
+//!
+//! ```text
+//! cargo bench --bench ranged [-- <filter>]
+//! ```
+//!
+//! Times are medians over a few samples; update and folds are per operation.
+//! `-` marks an operation the structure does not support for the monoid.
+//! Folds of [StringChain] cost as much as the length of the result, so its ranges are kept shorter than 64.
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+use rust_procon::algebra::typical::{AdditiveStruct, MaxMonoid, StringChain};
+use rust_procon::gen;
+use rust_procon::structure::ranged::accumulative_array::AccumulativeArray;
+use rust_procon::structure::ranged::block_sparse_table::BlockSparseTable;
+use rust_procon::structure::ranged::compact_segment_tree::CompactSegmentTree;
+use rust_procon::structure::ranged::fenwick_tree::FenwickTree;
+use rust_procon::structure::ranged::naive_vec::NaiveVec;
+use rust_procon::structure::ranged::segment_tree::SegmentTree;
+use rust_procon::structure::ranged::sparse_table::SparseTable;
+use rust_procon::structure::ranged::square_root_decomposition::SquareRootDecomposition;
+use rust_procon::structure::ranged::{LeftFixedFold, PointAssign, RangeFold};
+use std::hint::black_box;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+const SIZES: [usize; 2] = [100_000, 1_000_000];
+const SAMPLES: usize = 3;
+
+struct Input<E> {
+    data: Vec<E>,
+    updates: Vec<(usize, E)>,
+    ranges: Vec<Range<usize>>,
+}
+
+impl<E> Input<E> {
+    /// Random data, updates and ranges shorter than `max_len`.
+    fn new(
+        rng: &mut Pcg64,
+        n: usize,
+        ops: usize,
+        max_len: usize,
+        mut elem: impl FnMut(&mut Pcg64) -> E,
+    ) -> Self {
+        let data = (0..n).map(|_| elem(rng)).collect();
+        let updates = (0..ops).map(|_| (rng.gen_range(0..n), elem(rng))).collect();
+        let ranges = (0..ops)
+            .map(|_| {
+                let r = gen::range(rng, n);
+                r.start..r.end.min(r.start.saturating_add(max_len - 1))
+            })
+            .collect();
+        Self {
+            data,
+            updates,
+            ranges,
+        }
+    }
+}
+
+struct Row {
+    monoid: &'static str,
+    structure: &'static str,
+    n: usize,
+    build: Duration,
+    update: Option<Duration>,
+    prefix: Option<Duration>,
+    range: Option<Duration>,
+}
+
+fn set<E, T, S: PointAssign<E, T>>(s: &mut S, x: E, i: usize) {
+    s.set_at(x, i)
+}
+
+fn prefix<E, T, S: LeftFixedFold<E, T>>(s: &mut S, r: usize) -> E {
+    s.fold_to(r)
+}
+
+fn range<E, T, S: RangeFold<E, T>>(s: &mut S, r: Range<usize>) -> E {
+    s.fold_in(r)
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+/// Median time per call of `f` over `SAMPLES` runs of `ops` calls each.
+fn per_op(ops: usize, mut f: impl FnMut(usize)) -> Duration {
+    median(
+        (0..SAMPLES)
+            .map(|_| {
+                let start = Instant::now();
+                (0..ops).for_each(&mut f);
+                start.elapsed() / ops.max(1) as u32
+            })
+            .collect(),
+    )
+}
+
+struct Bench<'a, E> {
+    monoid: &'static str,
+    input: &'a Input<E>,
+    filter: &'a str,
+    rows: &'a mut Vec<Row>,
+}
+
+impl<E: Clone> Bench<'_, E> {
+    /// Measures the supported operations, running `ops` of each.
+    fn case<S>(
+        &mut self,
+        structure: &'static str,
+        ops: usize,
+        build: fn(&[E]) -> S,
+        update: Option<fn(&mut S, E, usize)>,
+        fold_to: Option<fn(&mut S, usize) -> E>,
+        fold_in: Option<fn(&mut S, Range<usize>) -> E>,
+    ) {
+        if !format!("{}/{}", self.monoid, structure).contains(self.filter) {
+            return;
+        }
+        let input = self.input;
+        let build_time = median(
+            (0..SAMPLES)
+                .map(|_| {
+                    let start = Instant::now();
+                    let s = black_box(build(&input.data));
+                    let elapsed = start.elapsed();
+                    drop(s);
+                    elapsed
+                })
+                .collect(),
+        );
+        let mut s = build(&input.data);
+        let update = update.map(|f| {
+            per_op(ops, |i| {
+                let (j, x) = input.updates[i].clone();
+                f(&mut s, x, j)
+            })
+        });
+        let prefix =
+            fold_to.map(|f| per_op(ops, |i| drop(black_box(f(&mut s, input.ranges[i].end)))));
+        let range =
+            fold_in.map(|f| per_op(ops, |i| drop(black_box(f(&mut s, input.ranges[i].clone())))));
+        self.rows.push(Row {
+            monoid: self.monoid,
+            structure,
+            n: input.data.len(),
+            build: build_time,
+            update,
+            prefix,
+            range,
+        });
+    }
+}
+
+fn bench_max(b: &mut Bench<i64>, ops: usize) {
+    type M = MaxMonoid;
+    b.case(
+        "NaiveVec",
+        ops / 1000,
+        |a| NaiveVec::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "AccumulativeArray",
+        ops,
+        |a| AccumulativeArray::<i64, M>::from(a),
+        None,
+        Some(prefix::<_, M, _>),
+        None,
+    );
+    b.case(
+        "FenwickTree",
+        ops,
+        |a| FenwickTree::<i64, M>::from(a),
+        None,
+        Some(prefix::<_, M, _>),
+        None,
+    );
+    b.case(
+        "SegmentTree",
+        ops,
+        |a| SegmentTree::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "CompactSegmentTree",
+        ops,
+        |a| CompactSegmentTree::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "SparseTable",
+        ops,
+        |a| SparseTable::<i64, M>::from(a),
+        None,
+        None,
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "BlockSparseTable",
+        ops,
+        |a| BlockSparseTable::<i64, M>::from(a),
+        None,
+        None,
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "SquareRootDecomposition",
+        ops,
+        |a| SquareRootDecomposition::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+}
+
+fn bench_add(b: &mut Bench<i64>, ops: usize) {
+    type M = AdditiveStruct;
+    b.case(
+        "NaiveVec",
+        ops / 1000,
+        |a| NaiveVec::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "AccumulativeArray",
+        ops,
+        |a| AccumulativeArray::<i64, M>::from(a),
+        None,
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "FenwickTree",
+        ops,
+        |a| FenwickTree::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "SegmentTree",
+        ops,
+        |a| SegmentTree::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "CompactSegmentTree",
+        ops,
+        |a| CompactSegmentTree::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "SquareRootDecomposition",
+        ops,
+        |a| SquareRootDecomposition::<i64, M>::from(a),
+        Some(set::<_, M, _>),
+        Some(prefix::<_, M, _>),
+        Some(range::<_, M, _>),
+    );
+}
+
+/// [AccumulativeArray] is skipped, since its prefixes take O(N^2) memory.
+/// Prefix folds are skipped too, since their results are as long as N.
+fn bench_string(b: &mut Bench<String>, ops: usize) {
+    type M = StringChain;
+    b.case(
+        "NaiveVec",
+        ops,
+        |a| NaiveVec::<String, M>::from(a),
+        Some(set::<_, M, _>),
+        None,
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "FenwickTree",
+        ops,
+        |a| FenwickTree::<String, M>::from(a),
+        None,
+        None,
+        None,
+    );
+    b.case(
+        "SegmentTree",
+        ops,
+        |a| SegmentTree::<String, M>::from(a),
+        Some(set::<_, M, _>),
+        None,
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "CompactSegmentTree",
+        ops,
+        |a| CompactSegmentTree::<String, M>::from(a),
+        Some(set::<_, M, _>),
+        None,
+        Some(range::<_, M, _>),
+    );
+    b.case(
+        "SquareRootDecomposition",
+        ops,
+        |a| SquareRootDecomposition::<String, M>::from(a),
+        Some(set::<_, M, _>),
+        None,
+        Some(range::<_, M, _>),
+    );
+}
+
+fn format_ns(d: Option<Duration>) -> String {
+    d.map_or("-".to_string(), |d| d.as_nanos().to_string())
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`
+    let filter = std::env::args()
+        .skip(1)
+        .find(|a| !a.starts_with("--"))
+        .unwrap_or_default();
+    let mut rng = Pcg64::seed_from_u64(47);
+    let mut rows = vec![];
+    for n in SIZES {
+        let ops = 100_000;
+        let input = Input::new(&mut rng, n, ops, usize::MAX, |rng| {
+            rng.gen_range(-1_000_000_000..1_000_000_000)
+        });
+        let mut b = Bench {
+            monoid: "MaxMonoid",
+            input: &input,
+            filter: &filter,
+            rows: &mut rows,
+        };
+        bench_max(&mut b, ops);
+        b.monoid = "AdditiveStruct";
+        bench_add(&mut b, ops);
+
+        let ops = 1000;
+        let input = Input::new(&mut rng, n, ops, 64, |rng| {
+            gen::string(rng, 1, b"abcdefghijklmnopqrstuvwxyz")
+        });
+        let mut b = Bench {
+            monoid: "StringChain",
+            input: &input,
+            filter: &filter,
+            rows: &mut rows,
+        };
+        bench_string(&mut b, ops);
+    }
+    rows.sort_by_key(|r| (r.monoid, r.structure, r.n));
+    println!(
+        "{:<16}{:<26}{:>9}{:>12}{:>12}{:>12}{:>12}",
+        "monoid", "structure", "N", "build(ms)", "update(ns)", "prefix(ns)", "range(ns)"
+    );
+    for r in &rows {
+        println!(
+            "{:<16}{:<26}{:>9}{:>12.2}{:>12}{:>12}{:>12}",
+            r.monoid,
+            r.structure,
+            r.n,
+            r.build.as_secs_f64() * 1e3,
+            format_ns(r.update),
+            format_ns(r.prefix),
+            format_ns(r.range),
+        );
+    }
+}
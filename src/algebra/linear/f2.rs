@@ -0,0 +1,173 @@
+use super::LinearSolution;
+use bitvec::vec::BitVec;
+
+/// Transforms `a` into the reduced row echelon form, and returns the pivot columns: O(NM min(N, M) / w).
+pub fn row_echelon_f2(a: &mut [BitVec]) -> Vec<usize> {
+    let m = a.first().map_or(0, BitVec::len);
+    let mut pivots = vec![];
+    for c in 0..m {
+        let r = pivots.len();
+        let Some(p) = (r..a.len()).find(|&i| a[i][c]) else {
+            continue;
+        };
+        a.swap(r, p);
+        let pivot_row = a[r].clone();
+        for (i, row) in a.iter_mut().enumerate() {
+            if i != r && row[c] {
+                *row ^= &pivot_row;
+            }
+        }
+        pivots.push(c);
+        if pivots.len() == a.len() {
+            break;
+        }
+    }
+    pivots
+}
+
+pub fn rank_f2(mut a: Vec<BitVec>) -> usize {
+    row_echelon_f2(&mut a).len()
+}
+
+/// `det a = 1` iff `a` is regular.
+pub fn determinant_f2(a: Vec<BitVec>) -> bool {
+    let n = a.len();
+    assert!(a.iter().all(|row| row.len() == n));
+    rank_f2(a) == n
+}
+
+/// The inverse matrix, if `a` is regular: O(N^3 / w).
+pub fn inverse_f2(a: &[BitVec]) -> Option<Vec<BitVec>> {
+    let n = a.len();
+    let mut b = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            assert_eq!(row.len(), n);
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| i == j));
+            row
+        })
+        .collect::<Vec<_>>();
+    let pivots = row_echelon_f2(&mut b);
+    if pivots.len() < n || pivots[n - 1] >= n {
+        return None;
+    }
+    Some(b.into_iter().map(|row| row[n..].to_bitvec()).collect())
+}
+
+/// Solves `a x = b` over F2 (a system of XOR equations).
+pub fn solve_f2(a: &[BitVec], b: &BitVec) -> Option<LinearSolution<BitVec>> {
+    assert_eq!(a.len(), b.len());
+    let m = a.first().map_or(0, BitVec::len);
+    let mut aug = a
+        .iter()
+        .zip(b.iter().by_vals())
+        .map(|(row, y)| {
+            assert_eq!(row.len(), m);
+            let mut row = row.clone();
+            row.push(y);
+            row
+        })
+        .collect::<Vec<_>>();
+    let pivots = row_echelon_f2(&mut aug);
+    if pivots.last() == Some(&m) {
+        return None;
+    }
+    let mut particular = BitVec::repeat(false, m);
+    for (row, &c) in aug.iter().zip(&pivots) {
+        particular.set(c, row[m]);
+    }
+    let mut is_pivot = vec![false; m];
+    pivots.iter().for_each(|&c| is_pivot[c] = true);
+    let kernel = (0..m)
+        .filter(|&f| !is_pivot[f])
+        .map(|f| {
+            let mut v = BitVec::repeat(false, m);
+            v.set(f, true);
+            for (row, &c) in aug.iter().zip(&pivots) {
+                v.set(c, row[f]);
+            }
+            v
+        })
+        .collect();
+    Some(LinearSolution { particular, kernel })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::linear::rank;
+    use ac_library::modint::{Barrett, DynamicModInt, Id};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum Two {}
+
+    impl Id for Two {
+        fn companion_barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::new(2);
+            &BARRETT
+        }
+    }
+
+    fn mul(a: &[BitVec], x: &BitVec) -> BitVec {
+        a.iter()
+            .map(|row| (row.clone() & x).count_ones() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn f2_agree_with_field() {
+        let mut rng = Pcg64::seed_from_u64(381);
+        for _ in 0..300 {
+            let (n, m) = (rng.gen_range(1..9), rng.gen_range(1..70));
+            let a = (0..n)
+                .map(|_| (0..m).map(|_| rng.gen_bool(0.3)).collect::<BitVec>())
+                .collect::<Vec<_>>();
+            let field = a
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .by_vals()
+                        .map(|b| DynamicModInt::<Two>::new(u32::from(b)))
+                        .collect()
+                })
+                .collect();
+            let rk = rank_f2(a.clone());
+            assert_eq!(rk, rank(field));
+
+            let x = (0..m).map(|_| rng.gen_bool(0.5)).collect::<BitVec>();
+            let b = mul(&a, &x);
+            let sol = solve_f2(&a, &b).unwrap();
+            assert_eq!(mul(&a, &sol.particular), b);
+            assert_eq!(sol.kernel.len(), m - rk);
+            for v in &sol.kernel {
+                assert!(mul(&a, v).not_any());
+            }
+        }
+    }
+
+    #[test]
+    fn f2_inverse() {
+        let mut rng = Pcg64::seed_from_u64(382);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..10);
+            let a = (0..n)
+                .map(|_| (0..n).map(|_| rng.gen_bool(0.5)).collect::<BitVec>())
+                .collect::<Vec<_>>();
+            match inverse_f2(&a) {
+                Some(inv) => {
+                    assert!(determinant_f2(a.clone()));
+                    for j in 0..n {
+                        let col = inv.iter().map(|row| row[j]).collect::<BitVec>();
+                        let e = mul(&a, &col);
+                        assert!(e.iter().by_vals().enumerate().all(|(i, y)| y == (i == j)));
+                    }
+                }
+                None => assert!(!determinant_f2(a)),
+            }
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use ac_library::modint::{DynamicModInt, Id, ModIntBase, Modulus, StaticModInt};
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::{NumAssign, Signed};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Gaussian elimination over F2, with rows packed into [bitvec::vec::BitVec].
+pub mod f2;
+
+/// A field for Gaussian elimination.
+///
+/// Exact fields take the first nonzero pivot. Floats take the pivot of the largest magnitude,
+/// and regard values within [Field::is_zero]'s tolerance as zero.
+pub trait Field:
+    Copy
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// `1 / self` for a nonzero `self`.
+    fn inv(self) -> Self;
+    fn is_zero(self) -> bool;
+    /// Whether `self` is preferred to `other` as a pivot.
+    fn is_better_pivot(self, _other: Self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_field_modint {
+    ($t:ty, $($bound:tt)*) => {
+        impl<$($bound)*> Field for $t {
+            fn zero() -> Self {
+                Self::raw(0)
+            }
+            fn one() -> Self {
+                Self::raw(1)
+            }
+            fn inv(self) -> Self {
+                ModIntBase::inv(self)
+            }
+            fn is_zero(self) -> bool {
+                self.val() == 0
+            }
+        }
+    };
+}
+
+impl_field_modint!(StaticModInt<M>, M: Modulus);
+impl_field_modint!(DynamicModInt<I>, I: Id);
+
+macro_rules! impl_field_float {
+    ($t:ty, $eps:expr) => {
+        impl Field for $t {
+            fn zero() -> Self {
+                0.0
+            }
+            fn one() -> Self {
+                1.0
+            }
+            fn inv(self) -> Self {
+                1.0 / self
+            }
+            fn is_zero(self) -> bool {
+                self.abs() < $eps
+            }
+            fn is_better_pivot(self, other: Self) -> bool {
+                self.abs() > other.abs()
+            }
+        }
+    };
+}
+
+impl_field_float!(f32, 1e-5);
+impl_field_float!(f64, 1e-9);
+
+impl<T: Copy + Integer + Signed + NumAssign> Field for Ratio<T> {
+    fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+    fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+    fn inv(self) -> Self {
+        self.recip()
+    }
+    fn is_zero(self) -> bool {
+        *self.numer() == T::zero()
+    }
+}
+
+/// A solution set `{ particular + Σ c_i kernel_i }` of a linear system.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LinearSolution<V> {
+    pub particular: V,
+    /// A basis of the kernel of the coefficient matrix.
+    pub kernel: Vec<V>,
+}
+
+/// The row in `rows` to pivot on in the column `c`.
+fn find_pivot<F: Field>(a: &[Vec<F>], rows: std::ops::Range<usize>, c: usize) -> Option<usize> {
+    rows.filter(|&i| !a[i][c].is_zero()).reduce(|p, i| {
+        if a[i][c].is_better_pivot(a[p][c]) {
+            i
+        } else {
+            p
+        }
+    })
+}
+
+/// Transforms `a` into the reduced row echelon form, and returns the pivot columns: O(NM min(N, M)).
+///
+/// The number of pivots is the rank.
+pub fn row_echelon<F: Field>(a: &mut [Vec<F>]) -> Vec<usize> {
+    let m = a.first().map_or(0, Vec::len);
+    let mut pivots = vec![];
+    for c in 0..m {
+        let r = pivots.len();
+        let Some(p) = find_pivot(a, r..a.len(), c) else {
+            continue;
+        };
+        a.swap(r, p);
+        let inv = a[r][c].inv();
+        a[r].iter_mut().for_each(|x| *x *= inv);
+        let pivot_row = a[r].clone();
+        for (i, row) in a.iter_mut().enumerate() {
+            let f = row[c];
+            if i != r && !f.is_zero() {
+                row.iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(x, &y)| *x -= f * y);
+            }
+        }
+        pivots.push(c);
+        if pivots.len() == a.len() {
+            break;
+        }
+    }
+    pivots
+}
+
+pub fn rank<F: Field>(mut a: Vec<Vec<F>>) -> usize {
+    row_echelon(&mut a).len()
+}
+
+/// The determinant over a field: O(N^3).
+pub fn determinant<F: Field>(mut a: Vec<Vec<F>>) -> F {
+    let n = a.len();
+    assert!(a.iter().all(|row| row.len() == n));
+    let mut det = F::one();
+    for c in 0..n {
+        let Some(p) = find_pivot(&a, c..n, c) else {
+            return F::zero();
+        };
+        if p != c {
+            a.swap(c, p);
+            det = -det;
+        }
+        det *= a[c][c];
+        let inv = a[c][c].inv();
+        for i in c + 1..n {
+            let f = a[i][c] * inv;
+            if !f.is_zero() {
+                let (top, bottom) = a.split_at_mut(i);
+                for (x, &y) in bottom[0][c..].iter_mut().zip(&top[c][c..]) {
+                    *x -= f * y;
+                }
+            }
+        }
+    }
+    det
+}
+
+/// The determinant without divisions, valid for any modulus including composite ones: O(N^3 logM).
+///
+/// Rows are reduced by the Euclidean algorithm on the representatives.
+pub fn determinant_ring<Z: ModIntBase>(mut a: Vec<Vec<Z>>) -> Z {
+    let n = a.len();
+    assert!(a.iter().all(|row| row.len() == n));
+    let mut det = Z::raw(1);
+    for c in 0..n {
+        for i in c + 1..n {
+            while a[i][c] != Z::raw(0) {
+                let q = Z::raw(a[c][c].val() / a[i][c].val());
+                let (top, bottom) = a.split_at_mut(i);
+                for (x, &y) in top[c][c..].iter_mut().zip(&bottom[0][c..]) {
+                    *x -= q * y;
+                }
+                a.swap(c, i);
+                det = -det;
+            }
+        }
+        det *= a[c][c];
+    }
+    det
+}
+
+/// The inverse matrix, if `a` is regular: O(N^3).
+pub fn inverse<F: Field>(a: &[Vec<F>]) -> Option<Vec<Vec<F>>> {
+    let n = a.len();
+    let mut b = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            assert_eq!(row.len(), n);
+            let mut row = row.clone();
+            row.extend((0..n).map(|j| if i == j { F::one() } else { F::zero() }));
+            row
+        })
+        .collect::<Vec<_>>();
+    let pivots = row_echelon(&mut b);
+    if pivots.len() < n || pivots[n - 1] >= n {
+        return None;
+    }
+    Some(b.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+/// Solves `a x = b`, for `a` of `N x M`: O(NM min(N, M)).
+pub fn solve<F: Field>(a: &[Vec<F>], b: &[F]) -> Option<LinearSolution<Vec<F>>> {
+    assert_eq!(a.len(), b.len());
+    let m = a.first().map_or(0, Vec::len);
+    let mut aug = a
+        .iter()
+        .zip(b)
+        .map(|(row, &y)| {
+            assert_eq!(row.len(), m);
+            let mut row = row.clone();
+            row.push(y);
+            row
+        })
+        .collect::<Vec<_>>();
+    let pivots = row_echelon(&mut aug);
+    if pivots.last() == Some(&m) {
+        return None;
+    }
+    let mut particular = vec![F::zero(); m];
+    for (row, &c) in aug.iter().zip(&pivots) {
+        particular[c] = row[m];
+    }
+    let mut is_pivot = vec![false; m];
+    pivots.iter().for_each(|&c| is_pivot[c] = true);
+    let kernel = (0..m)
+        .filter(|&f| !is_pivot[f])
+        .map(|f| {
+            let mut v = vec![F::zero(); m];
+            v[f] = F::one();
+            for (row, &c) in aug.iter().zip(&pivots) {
+                v[c] = -row[f];
+            }
+            v
+        })
+        .collect();
+    Some(LinearSolution { particular, kernel })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ac_library::modint::{Barrett, DynamicModInt, Id};
+    use ac_library::ModInt998244353 as Mint;
+    use num_rational::Ratio;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn mul<F: Field>(a: &[Vec<F>], x: &[F]) -> Vec<F> {
+        a.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(x)
+                    .map(|(&p, &q)| p * q)
+                    .fold(F::zero(), |acc, y| acc + y)
+            })
+            .collect()
+    }
+
+    /// `N x M` of rank at most `r`, as a product of `N x r` and `r x M`.
+    fn random_matrix(rng: &mut Pcg64, n: usize, m: usize, r: usize) -> Vec<Vec<Mint>> {
+        let p = (0..n)
+            .map(|_| {
+                (0..r)
+                    .map(|_| Mint::new(rng.gen_range(0..3)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let q = (0..r)
+            .map(|_| {
+                (0..m)
+                    .map(|_| Mint::new(rng.gen_range(0..3)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        p.iter()
+            .map(|row| {
+                (0..m)
+                    .map(|j| row.iter().zip(&q).map(|(&x, qr)| x * qr[j]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The sum over permutations.
+    fn determinant_naive<F: Field>(a: &[Vec<F>]) -> F {
+        fn rec<F: Field>(a: &[Vec<F>], i: usize, used: &mut [bool]) -> F {
+            if i == a.len() {
+                return F::one();
+            }
+            let mut res = F::zero();
+            let mut sign = F::one();
+            for j in 0..a.len() {
+                if used[j] {
+                    continue;
+                }
+                used[j] = true;
+                res += sign * a[i][j] * rec(a, i + 1, used);
+                used[j] = false;
+                sign = -sign;
+            }
+            res
+        }
+        rec(a, 0, &mut vec![false; a.len()])
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum Composite {}
+
+    impl Id for Composite {
+        fn companion_barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::new(5040);
+            &BARRETT
+        }
+    }
+
+    #[test]
+    fn linear_determinant() {
+        let mut rng = Pcg64::seed_from_u64(38);
+        for n in 0..6 {
+            for _ in 0..20 {
+                let a = (0..n)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| Mint::new(rng.gen_range(0..5)))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                let expected = determinant_naive(&a);
+                assert_eq!(determinant(a.clone()), expected);
+                assert_eq!(determinant_ring(a), expected);
+
+                let b = (0..n)
+                    .map(|_| {
+                        (0..n)
+                            .map(|_| DynamicModInt::<Composite>::new(rng.gen_range(0..5040)))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(determinant_ring(b.clone()), determinant_naive(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn linear_solve_inverse() {
+        let mut rng = Pcg64::seed_from_u64(380);
+        for _ in 0..200 {
+            let (n, m) = (rng.gen_range(1..7), rng.gen_range(1..7));
+            let r = rng.gen_range(0..=n.min(m));
+            let a = random_matrix(&mut rng, n, m, r);
+            let rk = rank(a.clone());
+            assert!(rk <= r);
+
+            let x = (0..m)
+                .map(|_| Mint::new(rng.gen::<u32>()))
+                .collect::<Vec<_>>();
+            let b = mul(&a, &x);
+            let sol = solve(&a, &b).unwrap();
+            assert_eq!(mul(&a, &sol.particular), b);
+            assert_eq!(sol.kernel.len(), m - rk);
+            for v in &sol.kernel {
+                assert!(mul(&a, v).iter().all(|&y| y == Mint::new(0)));
+            }
+            if rk < n {
+                // some b out of the image is unsolvable
+                assert!((0..n).any(|i| {
+                    let mut e = vec![Mint::new(0); n];
+                    e[i] = Mint::new(1);
+                    solve(&a, &e).is_none()
+                }));
+            }
+
+            if n == m {
+                match inverse(&a) {
+                    Some(inv) => {
+                        assert_eq!(rk, n);
+                        for (j, col) in
+                            (0..n).map(|j| (j, inv.iter().map(|row| row[j]).collect::<Vec<_>>()))
+                        {
+                            let e = mul(&a, &col);
+                            assert!(e
+                                .iter()
+                                .enumerate()
+                                .all(|(i, &y)| y == Mint::new(u32::from(i == j))));
+                        }
+                    }
+                    None => assert!(rk < n),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn linear_float() {
+        let mut rng = Pcg64::seed_from_u64(381);
+        for n in 1..8 {
+            // diagonally dominant, so well-conditioned
+            let a = (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| rng.gen_range(-1.0..1.0) + if i == j { n as f64 } else { 0.0 })
+                        .collect::<Vec<f64>>()
+                })
+                .collect::<Vec<_>>();
+            let x = (0..n).map(|_| rng.gen_range(-5.0..5.0)).collect::<Vec<_>>();
+            let sol = solve(&a, &mul(&a, &x)).unwrap();
+            assert!(sol.kernel.is_empty());
+            assert!(sol
+                .particular
+                .iter()
+                .zip(&x)
+                .all(|(p, q)| (p - q).abs() < 1e-9));
+            let inv = inverse(&a).unwrap();
+            for (j, col) in (0..n).map(|j| (j, inv.iter().map(|row| row[j]).collect::<Vec<_>>())) {
+                let e = mul(&a, &col);
+                assert!(e
+                    .iter()
+                    .enumerate()
+                    .all(|(i, &y)| (y - f64::from(u8::from(i == j))).abs() < 1e-9));
+            }
+            let det = determinant(a.clone());
+            assert!((det - determinant_naive(&a)).abs() < 1e-6 * det.abs().max(1.0));
+        }
+        // the third row is `0.1 r0 + 0.3 r1`, not exactly representable
+        let r0 = vec![1.0, 2.0, 3.0];
+        let r1 = vec![0.7, -1.1, 0.3];
+        let r2 = r0.iter().zip(&r1).map(|(p, q)| 0.1 * p + 0.3 * q).collect();
+        let a = vec![r0, r1, r2];
+        assert_eq!(rank(a.clone()), 2);
+        assert!(inverse(&a).is_none());
+        assert!(determinant(a).abs() < 1e-9);
+    }
+
+    #[test]
+    fn linear_rational() {
+        let mut rng = Pcg64::seed_from_u64(382);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..5);
+            let a = (0..n)
+                .map(|_| {
+                    (0..n)
+                        .map(|_| Ratio::new(rng.gen_range(-5i64..5), rng.gen_range(1..4)))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let det = determinant(a.clone());
+            assert_eq!(det, determinant_naive(&a));
+            match inverse(&a) {
+                Some(inv) => {
+                    assert_ne!(det, Ratio::from_integer(0));
+                    for (j, col) in
+                        (0..n).map(|j| (j, inv.iter().map(|row| row[j]).collect::<Vec<_>>()))
+                    {
+                        let e = mul(&a, &col);
+                        assert!(e
+                            .iter()
+                            .enumerate()
+                            .all(|(i, &y)| y == Ratio::from_integer(i64::from(i == j))));
+                    }
+                }
+                None => assert_eq!(det, Ratio::from_integer(0)),
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use super::*;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut, Mul};
+
+/// Matrix over the semiring `(E, A, M)`: `A` is the addition and `M` is the multiplication.
+///
+/// e.g. `Matrix<u64, AdditiveStruct, MultiplicativeStruct>` for counting,
+/// `Matrix<i64, MinMonoid, AdditiveStruct>` for the min-plus (tropical) semiring.
+///
+/// Products with `A::id()` are skipped, so the zero is treated as absorbing
+/// even if `M::op` would overflow on it (such as `i64::MAX + x`).
+#[derive(Debug)]
+pub struct Matrix<E, A, M> {
+    alg: PhantomData<(A, M)>,
+    rows: usize,
+    cols: usize,
+    data: Vec<E>,
+}
+
+// Implemented by hand, since deriving them requires `A` and `M` to implement them too.
+impl<E: Clone, A, M> Clone for Matrix<E, A, M> {
+    fn clone(&self) -> Self {
+        Self {
+            alg: Default::default(),
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<E: PartialEq, A, M> PartialEq for Matrix<E, A, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.cols == other.cols && self.data == other.data
+    }
+}
+
+impl<E: Eq, A, M> Eq for Matrix<E, A, M> {}
+
+impl<E, A, M> Matrix<E, A, M>
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+    /// The `rows x cols` zero matrix.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            alg: Default::default(),
+            rows,
+            cols,
+            data: vec![A::id(); rows * cols],
+        }
+    }
+
+    /// The `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut res = Self::new(n, n);
+        for i in 0..n {
+            res[(i, i)] = M::id();
+        }
+        res
+    }
+
+    /// The `0 x 0` matrix, which is the identity of every size in [MatrixMul].
+    pub fn unit() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `self^k` for a square matrix: O(N^3 logk).
+    pub fn pow(&self, mut k: u64) -> Self {
+        assert_eq!(self.rows, self.cols);
+        let mut res = Self::identity(self.rows);
+        let mut base = self.clone();
+        while k > 0 {
+            if k & 1 == 1 {
+                res = &res * &base;
+            }
+            base = &base * &base;
+            k >>= 1;
+        }
+        res
+    }
+
+    /// The matrix-vector product `self v`.
+    pub fn apply(&self, v: &[E]) -> Vec<E> {
+        assert_eq!(self.cols, v.len());
+        let zero = A::id();
+        (0..self.rows)
+            .map(|i| {
+                let row = &self.data[i * self.cols..(i + 1) * self.cols];
+                row.iter()
+                    .zip(v)
+                    .filter(|&(a, b)| *a != zero && *b != zero)
+                    .fold(A::id(), |acc, (a, b)| A::op(&acc, &M::op(a, b)))
+            })
+            .collect()
+    }
+}
+
+impl<E, A, M> From<Vec<Vec<E>>> for Matrix<E, A, M> {
+    fn from(a: Vec<Vec<E>>) -> Self {
+        let rows = a.len();
+        let cols = a.first().map_or(0, Vec::len);
+        assert!(a.iter().all(|row| row.len() == cols));
+        Self {
+            alg: Default::default(),
+            rows,
+            cols,
+            data: a.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<E, A, M> Index<(usize, usize)> for Matrix<E, A, M> {
+    type Output = E;
+    fn index(&self, (i, j): (usize, usize)) -> &E {
+        assert!(i < self.rows && j < self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<E, A, M> IndexMut<(usize, usize)> for Matrix<E, A, M> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut E {
+        assert!(i < self.rows && j < self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// O(NMK). A `0 x 0` operand is regarded as the identity, but other empty shapes are not.
+impl<E, A, M> Mul for &Matrix<E, A, M>
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+    type Output = Matrix<E, A, M>;
+    fn mul(self, rhs: Self) -> Matrix<E, A, M> {
+        if self.rows == 0 && self.cols == 0 {
+            return rhs.clone();
+        }
+        if rhs.rows == 0 && rhs.cols == 0 {
+            return self.clone();
+        }
+        assert_eq!(self.cols, rhs.rows);
+        let zero = A::id();
+        let mut res = Matrix::new(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = &self[(i, k)];
+                if *a == zero {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    let b = &rhs[(k, j)];
+                    if *b != zero {
+                        res[(i, j)] = A::op(&res[(i, j)], &M::op(a, b));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<E, A, M> Mul for Matrix<E, A, M>
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+/// matrix multiplication: [Monoid] whose identity is [Matrix::unit], for dynamic DP on segment trees.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MatrixMul;
+
+impl<E, A, M> Magma<Matrix<E, A, M>> for MatrixMul
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+    fn op(lhs: &Matrix<E, A, M>, rhs: &Matrix<E, A, M>) -> Matrix<E, A, M> {
+        lhs * rhs
+    }
+}
+
+impl<E, A, M> Semigroup<Matrix<E, A, M>> for MatrixMul
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+}
+
+impl<E, A, M> Monoid<Matrix<E, A, M>> for MatrixMul
+where
+    E: Clone + PartialEq,
+    A: Monoid<E> + Commutativity<E>,
+    M: Monoid<E>,
+{
+    fn id() -> Matrix<E, A, M> {
+        Matrix::unit()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Matrix, MatrixMul};
+    use crate::algebra::typical::{AdditiveStruct, MinMonoid, MultiplicativeStruct};
+    use crate::structure::ranged::naive_vec::NaiveVec;
+    use crate::structure::ranged::segment_tree::SegmentTree;
+    use crate::structure::ranged::{PointAssign, RangeFold};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    type Counting = Matrix<u64, AdditiveStruct, MultiplicativeStruct>;
+    type Tropical = Matrix<i64, MinMonoid, AdditiveStruct>;
+
+    #[test]
+    fn matrix_fibonacci() {
+        let m = Counting::from(vec![vec![1, 1], vec![1, 0]]);
+        let (mut a, mut b) = (0u64, 1u64);
+        for k in 0..60 {
+            assert_eq!(m.pow(k)[(0, 1)], a);
+            assert_eq!(m.pow(k).apply(&[1, 0]), vec![b, a]);
+            (a, b) = (b, a + b);
+        }
+        assert_eq!(m.pow(0), Counting::identity(2));
+    }
+
+    #[test]
+    fn matrix_empty_shapes() {
+        let a = Counting::from(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(&Counting::unit() * &a, a);
+        assert_eq!(&a * &Counting::unit(), a);
+        // `0 x 2` times `2 x 2` is `0 x 2`, not the right operand
+        let p = &Counting::new(0, 2) * &a;
+        assert_eq!((p.rows(), p.cols()), (0, 2));
+        // `3 x 0` times `0 x 2` is the `3 x 2` zero matrix
+        let q = &Counting::new(3, 0) * &Counting::new(0, 2);
+        assert_eq!(q, Counting::new(3, 2));
+        let r = &Counting::new(2, 0) * &Counting::unit();
+        assert_eq!((r.rows(), r.cols()), (2, 0));
+    }
+
+    #[test]
+    fn matrix_min_plus_walks() {
+        let mut rng = Pcg64::seed_from_u64(37);
+        let inf = i64::MAX;
+        for _ in 0..50 {
+            let n = rng.gen_range(1..6);
+            let mut g = Tropical::new(n, n);
+            for i in 0..n {
+                for j in 0..n {
+                    if rng.gen_bool(0.5) {
+                        g[(i, j)] = rng.gen_range(-5..10);
+                    }
+                }
+            }
+            // dist[v]: the shortest walk from 0 to v with exactly k edges
+            let mut dist = vec![inf; n];
+            dist[0] = 0;
+            for k in 0..8 {
+                let walks = g.pow(k);
+                for (v, &d) in dist.iter().enumerate() {
+                    assert_eq!(walks[(0, v)], d);
+                }
+                let mut next = vec![inf; n];
+                for u in 0..n {
+                    for v in 0..n {
+                        if dist[u] != inf && g[(u, v)] != inf {
+                            next[v] = next[v].min(dist[u] + g[(u, v)]);
+                        }
+                    }
+                }
+                dist = next;
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_segment_tree() {
+        let mut rng = Pcg64::seed_from_u64(370);
+        let random = |rng: &mut Pcg64| {
+            Counting::from(
+                (0..3)
+                    .map(|_| (0..3).map(|_| rng.gen_range(0..4)).collect())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let a = (0..13).map(|_| random(&mut rng)).collect::<Vec<_>>();
+        let mut nv = NaiveVec::<Counting, MatrixMul>::from(a.as_slice());
+        let mut st = SegmentTree::<Counting, MatrixMul>::from(a);
+        for _ in 0..50 {
+            let i = rng.gen_range(0..13);
+            let m = random(&mut rng);
+            nv.set_at(m.clone(), i);
+            st.set_at(m, i);
+            let l = rng.gen_range(0..=13);
+            let r = rng.gen_range(l..=13);
+            assert_eq!(st.fold_in(l..r), nv.fold_in(l..r));
+        }
+    }
+}
@@ -49,5 +49,13 @@ pub trait Commutativity<T>: Magma<T> {}
 /// gcd: `gcd x x = x`
 pub trait Idempotence<T>: Magma<T> {}
 
+/// Gaussian elimination over fields (prime fields, floats, rationals) and F2: rank, determinant, inverse and linear systems.
+pub mod linear;
+/// Matrices over a semiring given by two structures, and their multiplication [Monoid].
+pub mod matrix;
+/// Polynomial hash of sequences as a [Group] under concatenation.
+pub mod rolling_hash;
 /// Frequently used algebraic structures.
 pub mod typical;
+/// Linear bases over F2 for `u64` or wide bit vectors, mergeable as an [Idempotence] [Monoid].
+pub mod xor_basis;
@@ -0,0 +1,174 @@
+use super::*;
+use once_cell::sync::Lazy;
+use rand::Rng;
+
+/// 2^61 - 1, a Mersenne prime.
+pub const MOD: u64 = (1 << 61) - 1;
+
+/// Chosen once per process, so that adversarial inputs cannot be prepared.
+static BASE: Lazy<u64> = Lazy::new(|| rand::thread_rng().gen_range(1 << 40..MOD - 1));
+
+#[inline]
+fn mul(a: u64, b: u64) -> u64 {
+    let t = a as u128 * b as u128;
+    let t = (t >> 61) as u64 + (t as u64 & MOD);
+    if t >= MOD {
+        t - MOD
+    } else {
+        t
+    }
+}
+
+#[inline]
+fn add(a: u64, b: u64) -> u64 {
+    let t = a + b;
+    if t >= MOD {
+        t - MOD
+    } else {
+        t
+    }
+}
+
+fn pow(mut a: u64, mut e: u64) -> u64 {
+    let mut res = 1;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = mul(res, a);
+        }
+        a = mul(a, a);
+        e >>= 1;
+    }
+    res
+}
+
+/// Polynomial hash of a sequence `s`: `(Σ s_i base^{n-1-i}, base^n)` mod 2^61 - 1.
+///
+/// Since the length is kept as `base^n`, two hashes are equal iff (w.h.p.) the sequences are equal.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct RollingHash {
+    hash: u64,
+    power: u64,
+}
+
+impl RollingHash {
+    /// Hash of a sequence of length 1.
+    pub fn new(c: u64) -> Self {
+        Self {
+            hash: c % MOD,
+            power: *BASE,
+        }
+    }
+
+    pub fn from_slice<T: Copy + Into<u64>>(s: &[T]) -> Self {
+        s.iter().fold(RollingHashMonoid::id(), |acc, &c| {
+            RollingHashMonoid::op(&acc, &Self::new(c.into()))
+        })
+    }
+
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    pub fn base() -> u64 {
+        *BASE
+    }
+}
+
+/// concatenation of [RollingHash]: [Group]. Use [Dual](typical::Dual) for the reversed sequence.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct RollingHashMonoid;
+
+impl Magma<RollingHash> for RollingHashMonoid {
+    fn op(lhs: &RollingHash, rhs: &RollingHash) -> RollingHash {
+        RollingHash {
+            hash: add(mul(lhs.hash, rhs.power), rhs.hash),
+            power: mul(lhs.power, rhs.power),
+        }
+    }
+}
+
+impl Semigroup<RollingHash> for RollingHashMonoid {}
+
+impl Monoid<RollingHash> for RollingHashMonoid {
+    fn id() -> RollingHash {
+        RollingHash { hash: 0, power: 1 }
+    }
+}
+
+/// The power is never 0 since the base is non-zero, so `(h, p)^{-1} = (-h p^{-1}, p^{-1})`.
+impl Group<RollingHash> for RollingHashMonoid {
+    fn inv(elm: &RollingHash) -> RollingHash {
+        let power = pow(elm.power, MOD - 2);
+        RollingHash {
+            hash: (MOD - mul(elm.hash, power)) % MOD,
+            power,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RollingHash, RollingHashMonoid};
+    use crate::algebra::typical::Dual;
+    use crate::algebra::{Group, Magma, Monoid};
+    use crate::structure::ranged::accumulative_array::AccumulativeArray;
+    use crate::structure::ranged::segment_tree::SegmentTree;
+    use crate::structure::ranged::{PointAssign, RangeFold};
+
+    fn hashes(s: &[u8]) -> Vec<RollingHash> {
+        s.iter().map(|&c| RollingHash::new(c as u64)).collect()
+    }
+
+    #[test]
+    fn rolling_hash_group() {
+        let (a, b) = (
+            RollingHash::from_slice(b"abra"),
+            RollingHash::from_slice(b"cad"),
+        );
+        let ab = RollingHashMonoid::op(&a, &b);
+        assert_eq!(ab, RollingHash::from_slice(b"abracad"));
+        assert_eq!(RollingHashMonoid::op(&RollingHashMonoid::inv(&a), &ab), b);
+        assert_eq!(
+            RollingHashMonoid::op(&a, &RollingHashMonoid::inv(&a)),
+            RollingHashMonoid::id()
+        );
+        assert_ne!(a, RollingHash::from_slice(b"abr"));
+    }
+
+    #[test]
+    fn rolling_hash_substring() {
+        let s = b"abracadabra";
+        let n = s.len();
+        let mut ac = AccumulativeArray::<RollingHash, RollingHashMonoid>::from(hashes(s));
+        let mut st = SegmentTree::<RollingHash, RollingHashMonoid>::from(hashes(s));
+        for i in 0..=n {
+            for j in i..=n {
+                let expected = RollingHash::from_slice(&s[i..j]);
+                assert_eq!(ac.fold_in(i..j), expected);
+                assert_eq!(st.fold_in(i..j), expected);
+            }
+        }
+        assert_eq!(ac.fold_in(0..4), ac.fold_in(7..11));
+        st.set_at(RollingHash::new(b'o' as u64), 10);
+        assert_ne!(st.fold_in(0..4), st.fold_in(7..11));
+    }
+
+    #[test]
+    fn rolling_hash_palindrome() {
+        let mut s = b"abacabad".to_vec();
+        let n = s.len();
+        let mut fwd = SegmentTree::<RollingHash, RollingHashMonoid>::from(hashes(&s));
+        let mut rev = SegmentTree::<RollingHash, Dual<RollingHashMonoid>>::from(hashes(&s));
+        for (c, i) in [(b'a', 7), (b'c', 0), (b'd', 3)] {
+            s[i] = c;
+            fwd.set_at(RollingHash::new(c as u64), i);
+            rev.set_at(RollingHash::new(c as u64), i);
+            for l in 0..=n {
+                for r in l..=n {
+                    let is_palindrome = s[l..r].iter().eq(s[l..r].iter().rev());
+                    assert_eq!(fwd.fold_in(l..r) == rev.fold_in(l..r), is_palindrome);
+                }
+            }
+        }
+    }
+}
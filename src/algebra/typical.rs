@@ -1,6 +1,8 @@
 use super::*;
-use num_traits::Zero;
-use std::ops::{Add, BitXor, Neg};
+use num_integer::Integer;
+use num_traits::{One, Unsigned, Zero};
+use std::marker::PhantomData;
+use std::ops::{Add, BitXor, Mul, Neg};
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct BoundedVec<T>(Vec<T>);
@@ -23,7 +25,7 @@ pub struct MinMonoid;
 
 impl<T: Clone + Ord> Magma<T> for MinMonoid {
     fn op(lhs: &T, rhs: &T) -> T {
-        lhs.max(rhs).clone()
+        lhs.min(rhs).clone()
     }
 }
 
@@ -96,6 +98,30 @@ where
 
 impl<T> Commutativity<T> for AdditiveStruct where T: Clone + Add<Output = T> {}
 
+/// multiplicative: [Monoid]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct MultiplicativeStruct;
+
+impl<T> Magma<T> for MultiplicativeStruct
+where
+    T: Clone + Mul<Output = T>,
+{
+    fn op(lhs: &T, rhs: &T) -> T {
+        lhs.clone() * rhs.clone()
+    }
+}
+
+impl<T> Semigroup<T> for MultiplicativeStruct where T: Clone + Mul<Output = T> {}
+
+impl<T> Monoid<T> for MultiplicativeStruct
+where
+    T: Clone + Mul<Output = T> + One,
+{
+    fn id() -> T {
+        T::one()
+    }
+}
+
 /// bitwise-xor: [Group]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
 pub struct BitXorGroup;
@@ -149,9 +175,197 @@ impl Monoid<String> for StringChain {
     }
 }
 
+/// gcd: [Monoid], [Idempotence] and [Commutativity], whose identity is 0
+///
+/// Only for unsigned integers, since `gcd(-a, 0) = a` breaks the identity law.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct GcdMonoid;
+
+impl<T: Clone + Integer + Unsigned> Magma<T> for GcdMonoid {
+    fn op(lhs: &T, rhs: &T) -> T {
+        lhs.gcd(rhs)
+    }
+}
+
+impl<T: Clone + Integer + Unsigned> Semigroup<T> for GcdMonoid {}
+
+impl<T: Clone + Integer + Unsigned> Monoid<T> for GcdMonoid {
+    fn id() -> T {
+        T::zero()
+    }
+}
+
+impl<T: Clone + Integer + Unsigned> Idempotence<T> for GcdMonoid {}
+
+impl<T: Clone + Integer + Unsigned> Commutativity<T> for GcdMonoid {}
+
+/// lcm: [Monoid], [Idempotence] and [Commutativity], whose identity is 1. Beware of overflow.
+///
+/// Only for unsigned integers, as [GcdMonoid].
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct LcmMonoid;
+
+impl<T: Clone + Integer + Unsigned> Magma<T> for LcmMonoid {
+    fn op(lhs: &T, rhs: &T) -> T {
+        lhs.lcm(rhs)
+    }
+}
+
+impl<T: Clone + Integer + Unsigned> Semigroup<T> for LcmMonoid {}
+
+impl<T: Clone + Integer + Unsigned> Monoid<T> for LcmMonoid {
+    fn id() -> T {
+        T::one()
+    }
+}
+
+impl<T: Clone + Integer + Unsigned> Idempotence<T> for LcmMonoid {}
+
+impl<T: Clone + Integer + Unsigned> Commutativity<T> for LcmMonoid {}
+
+/// composition of affine functions `(a, b): x -> a x + b`: [Monoid]
+///
+/// `f op g` is "`f`, then `g`", i.e. `x -> g(f(x))`, so that folding `[l, r)` applies `f_l` first.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct AffineComposition;
+
+impl AffineComposition {
+    /// Returns `f(x)`.
+    pub fn apply<T>(f: &(T, T), x: &T) -> T
+    where
+        T: Clone + Add<Output = T> + Mul<Output = T>,
+    {
+        f.0.clone() * x.clone() + f.1.clone()
+    }
+}
+
+impl<T> Magma<(T, T)> for AffineComposition
+where
+    T: Clone + Add<Output = T> + Mul<Output = T>,
+{
+    fn op(f: &(T, T), g: &(T, T)) -> (T, T) {
+        (f.0.clone() * g.0.clone(), Self::apply(g, &f.1))
+    }
+}
+
+impl<T> Semigroup<(T, T)> for AffineComposition where T: Clone + Add<Output = T> + Mul<Output = T> {}
+
+impl<T> Monoid<(T, T)> for AffineComposition
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + Zero + One,
+{
+    fn id() -> (T, T) {
+        (T::one(), T::zero())
+    }
+}
+
+/// composition of permutations: [Group]
+///
+/// `p op q` is "`p`, then `q`", i.e. `i -> q[p[i]]`.
+/// Indices beyond the length are fixed points, so `vec![]` is the identity of every size;
+/// results are trimmed of trailing fixed points.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct PermutationComposition;
+
+impl PermutationComposition {
+    /// Returns `p(i)`.
+    pub fn apply(p: &[usize], i: usize) -> usize {
+        p.get(i).copied().unwrap_or(i)
+    }
+
+    fn trim(mut p: Vec<usize>) -> Vec<usize> {
+        while p.last().is_some_and(|&x| x + 1 == p.len()) {
+            p.pop();
+        }
+        p
+    }
+}
+
+impl Magma<Vec<usize>> for PermutationComposition {
+    fn op(p: &Vec<usize>, q: &Vec<usize>) -> Vec<usize> {
+        let n = p.len().max(q.len());
+        Self::trim((0..n).map(|i| Self::apply(q, Self::apply(p, i))).collect())
+    }
+}
+
+impl Semigroup<Vec<usize>> for PermutationComposition {}
+
+impl Monoid<Vec<usize>> for PermutationComposition {
+    fn id() -> Vec<usize> {
+        vec![]
+    }
+}
+
+impl Group<Vec<usize>> for PermutationComposition {
+    fn inv(p: &Vec<usize>) -> Vec<usize> {
+        let mut res = vec![0; p.len()];
+        for (i, &x) in p.iter().enumerate() {
+            res[x] = i;
+        }
+        Self::trim(res)
+    }
+}
+
+/// product of 2x2 matrices `[[a, b], [c, d]]`: [Monoid]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Matrix2Product;
+
+impl<T> Magma<[[T; 2]; 2]> for Matrix2Product
+where
+    T: Clone + Add<Output = T> + Mul<Output = T>,
+{
+    fn op(lhs: &[[T; 2]; 2], rhs: &[[T; 2]; 2]) -> [[T; 2]; 2] {
+        let e = |i: usize, j: usize| {
+            lhs[i][0].clone() * rhs[0][j].clone() + lhs[i][1].clone() * rhs[1][j].clone()
+        };
+        [[e(0, 0), e(0, 1)], [e(1, 0), e(1, 1)]]
+    }
+}
+
+impl<T> Semigroup<[[T; 2]; 2]> for Matrix2Product where T: Clone + Add<Output = T> + Mul<Output = T> {}
+
+impl<T> Monoid<[[T; 2]; 2]> for Matrix2Product
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + Zero + One,
+{
+    fn id() -> [[T; 2]; 2] {
+        [[T::one(), T::zero()], [T::zero(), T::one()]]
+    }
+}
+
+/// reversed op of `T`: `a op' b = b op a`. Inherits every property of `T`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Dual<T>(PhantomData<T>);
+
+impl<E, T: Magma<E>> Magma<E> for Dual<T> {
+    fn op(lhs: &E, rhs: &E) -> E {
+        T::op(rhs, lhs)
+    }
+}
+
+impl<E, T: Semigroup<E>> Semigroup<E> for Dual<T> {}
+
+impl<E, T: Monoid<E>> Monoid<E> for Dual<T> {
+    fn id() -> E {
+        T::id()
+    }
+}
+
+impl<E, T: Group<E>> Group<E> for Dual<T> {
+    fn inv(elm: &E) -> E {
+        T::inv(elm)
+    }
+}
+
+impl<E, T: Commutativity<E>> Commutativity<E> for Dual<T> {}
+
+impl<E, T: Idempotence<E>> Idempotence<E> for Dual<T> {}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::structure::ranged::segment_tree::SegmentTree;
+    use crate::structure::ranged::RangeFold;
 
     #[test]
     fn test_monoid_max() {
@@ -166,4 +380,87 @@ mod test {
             40,
         )
     }
+
+    #[test]
+    fn test_monoid_min() {
+        let (l, r) = (0, 3);
+        assert_eq!(MinMonoid::op(&l, &r), 0);
+        assert_eq!(MinMonoid::op(&r, &MinMonoid::id()), r);
+        assert_eq!(
+            (20usize..=40).fold(MinMonoid::id(), |ac, x| MinMonoid::op(&ac, &x)),
+            20,
+        )
+    }
+
+    #[test]
+    fn test_dual_string_chain() {
+        let (l, r) = (String::from("ab"), String::from("cd"));
+        assert_eq!(Dual::<StringChain>::op(&l, &r), "cdab");
+        assert_eq!(Dual::<StringChain>::op(&l, &Dual::<StringChain>::id()), l);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(GcdMonoid::op(&12u32, &18), 6);
+        assert_eq!(GcdMonoid::op(&12u32, &GcdMonoid::id()), 12);
+        assert_eq!(GcdMonoid::op(&GcdMonoid::id(), &12u32), 12);
+        assert_eq!(GcdMonoid::op(&12u32, &12), 12);
+        assert_eq!(LcmMonoid::op(&4u64, &6), 12);
+        assert_eq!(LcmMonoid::op(&7u64, &LcmMonoid::id()), 7);
+        assert_eq!(LcmMonoid::op(&LcmMonoid::id(), &7u64), 7);
+    }
+
+    #[test]
+    fn test_affine_composition() {
+        let (f, g) = ((2i64, 3i64), (-1i64, 5i64));
+        let fg = AffineComposition::op(&f, &g);
+        for x in -5..5 {
+            let expected = AffineComposition::apply(&g, &AffineComposition::apply(&f, &x));
+            assert_eq!(AffineComposition::apply(&fg, &x), expected);
+        }
+        assert_eq!(AffineComposition::op(&f, &AffineComposition::id()), f);
+        assert_eq!(AffineComposition::op(&AffineComposition::id(), &f), f);
+
+        let fs = (0..10).map(|i| (i % 3 - 1, i)).collect::<Vec<(i64, i64)>>();
+        let mut st = SegmentTree::<_, AffineComposition>::from(fs.as_slice());
+        for l in 0..=fs.len() {
+            for r in l..=fs.len() {
+                let expected = fs[l..r]
+                    .iter()
+                    .fold(7, |x, f| AffineComposition::apply(f, &x));
+                assert_eq!(AffineComposition::apply(&st.fold_in(l..r), &7), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutation_composition() {
+        let (p, q) = (vec![1, 2, 0], vec![0, 1, 3, 2]);
+        let pq = PermutationComposition::op(&p, &q);
+        assert_eq!(pq, vec![1, 3, 0, 2]);
+        assert_eq!(
+            PermutationComposition::op(&pq, &PermutationComposition::inv(&pq)),
+            vec![]
+        );
+        assert_eq!(
+            PermutationComposition::op(&PermutationComposition::inv(&pq), &pq),
+            vec![]
+        );
+        assert_eq!(
+            PermutationComposition::op(&vec![0, 2, 1], &vec![0, 2, 1]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_matrix2_fibonacci() {
+        let m = [[1u64, 1], [1, 0]];
+        let (mut a, mut b) = (0u64, 1u64);
+        let mut pw = Matrix2Product::id();
+        for _ in 0..50 {
+            assert_eq!(pw[0][1], a);
+            pw = Matrix2Product::op(&pw, &m);
+            (a, b) = (b, a + b);
+        }
+    }
 }
@@ -0,0 +1,298 @@
+use super::*;
+use bitvec::vec::BitVec;
+
+/// A vector over F2, where the `i`-th bit is the coefficient of `2^i`. [Default] is zero.
+pub trait XorVector: Clone + Eq + Default {
+    /// The highest set bit, or `None` for zero.
+    fn leading_bit(&self) -> Option<usize>;
+    fn bit(&self, i: usize) -> bool;
+    fn xor_assign(&mut self, rhs: &Self);
+}
+
+macro_rules! impl_xor_vector {
+    ($($t:ty),*) => {$(
+        impl XorVector for $t {
+            fn leading_bit(&self) -> Option<usize> {
+                self.checked_ilog2().map(|b| b as usize)
+            }
+
+            fn bit(&self, i: usize) -> bool {
+                self >> i & 1 == 1
+            }
+
+            fn xor_assign(&mut self, rhs: &Self) {
+                *self ^= rhs;
+            }
+        }
+    )*};
+}
+
+impl_xor_vector!(u8, u16, u32, u64, u128, usize);
+
+/// Vectors of any width. Shorter ones are regarded as padded with zeros.
+impl XorVector for BitVec {
+    fn leading_bit(&self) -> Option<usize> {
+        self.last_one()
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.get(i).is_some_and(|b| *b)
+    }
+
+    fn xor_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.resize(rhs.len(), false);
+        }
+        self[..rhs.len()] ^= rhs;
+    }
+}
+
+/// Linear basis of a subspace of F2^w, kept in the reduced row echelon form:
+/// the leading bits are distinct and cleared in every other element.
+///
+/// Since the form is unique for each subspace, `==` means the spans are equal,
+/// even between [BitVec]s of different widths.
+/// Insertion: O(w) operations on vectors.
+#[derive(Clone, Debug, Default)]
+pub struct XorBasis<V> {
+    /// sorted by the leading bits in descending order
+    basis: Vec<V>,
+}
+
+impl<V: XorVector> XorBasis<V> {
+    pub fn new() -> Self {
+        Self { basis: vec![] }
+    }
+
+    /// The dimension of the span.
+    pub fn rank(&self) -> usize {
+        self.basis.len()
+    }
+
+    pub fn basis(&self) -> &[V] {
+        &self.basis
+    }
+
+    fn lead(v: &V) -> usize {
+        v.leading_bit().unwrap()
+    }
+
+    fn reduce(&self, mut x: V) -> V {
+        for b in &self.basis {
+            if x.bit(Self::lead(b)) {
+                x.xor_assign(b);
+            }
+        }
+        x
+    }
+
+    /// Adds `x` into the span. Returns `false` if it is already representable.
+    pub fn insert(&mut self, x: V) -> bool {
+        let x = self.reduce(x);
+        let Some(h) = x.leading_bit() else {
+            return false;
+        };
+        for b in self.basis.iter_mut() {
+            if b.bit(h) {
+                b.xor_assign(&x);
+            }
+        }
+        let pos = self.basis.partition_point(|b| Self::lead(b) > h);
+        self.basis.insert(pos, x);
+        true
+    }
+
+    /// Whether `x` is the XOR of some subset.
+    pub fn contains(&self, x: &V) -> bool {
+        self.reduce(x.clone()).leading_bit().is_none()
+    }
+
+    /// The maximum representable value.
+    pub fn max(&self) -> V {
+        self.basis.iter().fold(V::default(), |mut acc, b| {
+            acc.xor_assign(b);
+            acc
+        })
+    }
+
+    /// The maximum of `x ^ v` over the representable `v`.
+    pub fn max_with(&self, mut x: V) -> V {
+        for b in &self.basis {
+            if !x.bit(Self::lead(b)) {
+                x.xor_assign(b);
+            }
+        }
+        x
+    }
+
+    /// The minimum non-zero representable value. (`0` is always representable.)
+    pub fn min(&self) -> Option<V> {
+        self.basis.last().cloned()
+    }
+
+    /// The `k`-th (0-indexed) smallest representable value, counting `0` as the 0-th.
+    pub fn kth(&self, k: u64) -> Option<V> {
+        let r = self.rank();
+        if r < 64 && k >> r != 0 {
+            return None;
+        }
+        let mut res = V::default();
+        for (i, b) in self.basis.iter().rev().enumerate().take(64) {
+            if k >> i & 1 == 1 {
+                res.xor_assign(b);
+            }
+        }
+        Some(res)
+    }
+}
+
+/// Compares the bits up to the leading ones, so that trailing zeros of [BitVec] do not matter.
+impl<V: XorVector> PartialEq for XorBasis<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+            && self.basis.iter().zip(&other.basis).all(|(x, y)| {
+                let h = Self::lead(x);
+                h == Self::lead(y) && (0..h).all(|i| x.bit(i) == y.bit(i))
+            })
+    }
+}
+
+impl<V: XorVector> Eq for XorBasis<V> {}
+
+impl<V: XorVector> FromIterator<V> for XorBasis<V> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        let mut res = Self::new();
+        iter.into_iter().for_each(|x| {
+            res.insert(x);
+        });
+        res
+    }
+}
+
+/// sum of subspaces: [Monoid], [Idempotence] and [Commutativity]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct XorBasisMerge;
+
+impl<V: XorVector> Magma<XorBasis<V>> for XorBasisMerge {
+    fn op(lhs: &XorBasis<V>, rhs: &XorBasis<V>) -> XorBasis<V> {
+        let (large, small) = if lhs.rank() >= rhs.rank() {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+        let mut res = large.clone();
+        for b in &small.basis {
+            res.insert(b.clone());
+        }
+        res
+    }
+}
+
+impl<V: XorVector> Semigroup<XorBasis<V>> for XorBasisMerge {}
+
+impl<V: XorVector> Monoid<XorBasis<V>> for XorBasisMerge {
+    fn id() -> XorBasis<V> {
+        XorBasis::new()
+    }
+}
+
+impl<V: XorVector> Idempotence<XorBasis<V>> for XorBasisMerge {}
+
+impl<V: XorVector> Commutativity<XorBasis<V>> for XorBasisMerge {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::structure::ranged::naive_vec::NaiveVec;
+    use crate::structure::ranged::segment_tree::SegmentTree;
+    use crate::structure::ranged::sparse_table::SparseTable;
+    use crate::structure::ranged::{PointAssign, RangeFold};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+    use std::collections::BTreeSet;
+
+    fn span(xs: &[u64]) -> BTreeSet<u64> {
+        let mut res = BTreeSet::from([0]);
+        for &x in xs {
+            let next = res.iter().map(|&v| v ^ x).collect::<Vec<_>>();
+            res.extend(next);
+        }
+        res
+    }
+
+    #[test]
+    fn xor_basis_queries() {
+        let mut rng = Pcg64::seed_from_u64(39);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..8);
+            let xs = (0..n).map(|_| rng.gen_range(0..256)).collect::<Vec<u64>>();
+            let expected = span(&xs);
+            let basis = xs.iter().copied().collect::<XorBasis<u64>>();
+            assert_eq!(1 << basis.rank(), expected.len());
+            for x in 0..256 {
+                assert_eq!(basis.contains(&x), expected.contains(&x));
+            }
+            assert_eq!(basis.max(), *expected.last().unwrap());
+            assert_eq!(basis.min(), expected.iter().nth(1).copied());
+            for (k, &v) in expected.iter().enumerate() {
+                assert_eq!(basis.kth(k as u64), Some(v));
+            }
+            assert_eq!(basis.kth(expected.len() as u64), None);
+            let x = rng.gen_range(0..256);
+            assert_eq!(
+                basis.max_with(x),
+                expected.iter().map(|&v| v ^ x).max().unwrap()
+            );
+
+            // the same subspace as a wide vector
+            let to_bits = |x: u64| {
+                (0..100)
+                    .map(|i| i < 64 && x >> i & 1 == 1)
+                    .collect::<BitVec>()
+            };
+            let wide = xs.iter().map(|&x| to_bits(x)).collect::<XorBasis<BitVec>>();
+            assert_eq!(wide.rank(), basis.rank());
+            let narrow = xs
+                .iter()
+                .map(|&x| to_bits(x)[..64].to_bitvec())
+                .collect::<XorBasis<BitVec>>();
+            assert_eq!(narrow, wide);
+            let mut larger = wide.clone();
+            assert!(larger.insert(to_bits(1 << 63)));
+            assert_ne!(larger, narrow);
+            for (k, &v) in expected.iter().enumerate() {
+                let kth = wide.kth(k as u64).unwrap();
+                assert!(kth.iter_ones().eq(to_bits(v).iter_ones()));
+                assert!(wide.contains(&to_bits(v)));
+            }
+        }
+    }
+
+    #[test]
+    fn xor_basis_range_fold() {
+        let mut rng = Pcg64::seed_from_u64(390);
+        let n = 30;
+        let a = (0..n)
+            .map(|_| XorBasis::from_iter([rng.gen_range(0..1u64 << 12)]))
+            .collect::<Vec<_>>();
+        let mut nv = NaiveVec::<_, XorBasisMerge>::from(a.as_slice());
+        let mut sp = SparseTable::<_, XorBasisMerge>::from(a.as_slice());
+        let mut st = SegmentTree::<_, XorBasisMerge>::from(a);
+        for l in 0..=n {
+            for r in l..=n {
+                let expected = nv.fold_in(l..r);
+                assert_eq!(sp.fold_in(l..r), expected);
+                assert_eq!(st.fold_in(l..r), expected);
+            }
+        }
+        for _ in 0..100 {
+            let i = rng.gen_range(0..n);
+            let b = XorBasis::from_iter([rng.gen_range(0..1u64 << 12)]);
+            nv.set_at(b.clone(), i);
+            st.set_at(b, i);
+            let l = rng.gen_range(0..=n);
+            let r = rng.gen_range(l..=n);
+            assert_eq!(st.fold_in(l..r), nv.fold_in(l..r));
+        }
+    }
+}
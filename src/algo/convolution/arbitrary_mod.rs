@@ -0,0 +1,111 @@
+use crate::algo::convolution::ntt::convolution_ntt;
+use ac_library::modint::{ButterflyCache, ModIntBase, Modulus, StaticModInt};
+use std::cell::RefCell;
+use std::thread::LocalKey;
+
+macro_rules! ntt_prime {
+    ($name:ident, $value:expr) => {
+        #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+        enum $name {}
+
+        impl Modulus for $name {
+            const VALUE: u32 = $value;
+            const HINT_VALUE_IS_PRIME: bool = true;
+
+            fn butterfly_cache() -> &'static LocalKey<RefCell<Option<ButterflyCache<Self>>>> {
+                thread_local! {
+                    static BUTTERFLY_CACHE: RefCell<Option<ButterflyCache<$name>>> = RefCell::default();
+                }
+                &BUTTERFLY_CACHE
+            }
+        }
+    };
+}
+
+// 2^24 | p - 1 for each of them, and their product is about 5.9 * 10^25.
+ntt_prime!(P1, 167_772_161);
+ntt_prime!(P2, 469_762_049);
+ntt_prime!(P3, 754_974_721);
+
+fn convolution_in<M: Modulus>(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let a = a
+        .iter()
+        .map(|&x| StaticModInt::<M>::new(x))
+        .collect::<Vec<_>>();
+    let b = b
+        .iter()
+        .map(|&x| StaticModInt::<M>::new(x))
+        .collect::<Vec<_>>();
+    convolution_ntt(&a, &b)
+        .into_iter()
+        .map(|x| x.val())
+        .collect()
+}
+
+/// Convolution modulo `m <= 2^32`, exact while `min(|a|, |b|) (m - 1)^2 < 5.9 * 10^25`
+/// (e.g. `m <= 2^30` and any length up to `2^24`, or `m < 2^31` and length up to `2^23`).
+pub fn convolution_u64_mod(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+    assert!((1..=1 << 32).contains(&m));
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    debug_assert!(
+        a.len().min(b.len()) as u128 * (m as u128 - 1).pow(2)
+            < P1::VALUE as u128 * P2::VALUE as u128 * P3::VALUE as u128,
+        "coefficients may exceed the product of the NTT primes"
+    );
+    let reduce = |v: &[u64]| v.iter().map(|&x| (x % m) as u32).collect::<Vec<_>>();
+    let (a, b) = (reduce(a), reduce(b));
+    let c1 = convolution_in::<P1>(&a, &b);
+    let c2 = convolution_in::<P2>(&a, &b);
+    let c3 = convolution_in::<P3>(&a, &b);
+    let (m1, m2, m3) = (P1::VALUE as u128, P2::VALUE as u128, P3::VALUE as u128);
+    let m1_inv_m2 = StaticModInt::<P2>::new(P1::VALUE).inv().val() as u128;
+    let m12_inv_m3 = StaticModInt::<P3>::new(m1 * m2).inv().val() as u128;
+    let m = m as u128;
+    // Garner: x = x1 + m1 (t2 + m2 t3), each t_i taken modulo p_i
+    c1.into_iter()
+        .zip(c2)
+        .zip(c3)
+        .map(|((x1, x2), x3)| {
+            let (x1, x2, x3) = (x1 as u128, x2 as u128, x3 as u128);
+            let t2 = (x2 + m2 - x1 % m2) * m1_inv_m2 % m2;
+            let x12 = x1 + m1 * t2;
+            let t3 = (x3 + m3 - x12 % m3) * m12_inv_m3 % m3;
+            ((x12 + m1 * m2 % m * t3) % m) as u64
+        })
+        .collect()
+}
+
+/// Convolution over any modulus of a modular integer type.
+pub fn convolution_arbitrary<Z: ModIntBase>(a: &[Z], b: &[Z]) -> Vec<Z> {
+    let to_u64 = |v: &[Z]| v.iter().map(|x| x.val() as u64).collect::<Vec<_>>();
+    convolution_u64_mod(&to_u64(a), &to_u64(b), Z::modulus() as u64)
+        .into_iter()
+        .map(|x| Z::raw(x as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::convolution_u64_mod;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn arbitrary_mod_random() {
+        let mut rng = Pcg64::seed_from_u64(340);
+        for m in [1, 2, 1_000_000_007, u32::MAX as u64, 1 << 32] {
+            let a = (0..200).map(|_| rng.gen_range(0..m)).collect::<Vec<_>>();
+            let b = (0..150).map(|_| rng.gen_range(0..m)).collect::<Vec<_>>();
+            let mut expected = vec![0u128; a.len() + b.len() - 1];
+            for (i, &x) in a.iter().enumerate() {
+                for (j, &y) in b.iter().enumerate() {
+                    expected[i + j] = (expected[i + j] + x as u128 * y as u128) % m as u128;
+                }
+            }
+            let expected = expected.into_iter().map(|x| x as u64).collect::<Vec<_>>();
+            assert_eq!(convolution_u64_mod(&a, &b, m), expected);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+/// `a_S <- Σ_{T ⊆ S} a_T`. With `inverse`, the Möbius transform undoing it.
+pub fn subset_zeta<T: Copy + Add<Output = T> + Sub<Output = T>>(a: &mut [T], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit != 0 {
+                a[s] = if inverse {
+                    a[s] - a[s ^ bit]
+                } else {
+                    a[s] + a[s ^ bit]
+                };
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// `a_S <- Σ_{T ⊇ S} a_T`. With `inverse`, the Möbius transform undoing it.
+pub fn superset_zeta<T: Copy + Add<Output = T> + Sub<Output = T>>(a: &mut [T], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit == 0 {
+                a[s] = if inverse {
+                    a[s] - a[s | bit]
+                } else {
+                    a[s] + a[s | bit]
+                };
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Walsh-Hadamard transform. With `inverse`, also divides by `a.len()`.
+pub fn walsh_hadamard<T>(a: &mut [T], inverse: bool)
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u32>,
+{
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let mut len = 1;
+    while len < n {
+        for chunk in a.chunks_mut(2 * len) {
+            let (lo, hi) = chunk.split_at_mut(len);
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                (*x, *y) = (*x + *y, *x - *y);
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        let n = T::from(n as u32);
+        a.iter_mut().for_each(|x| *x = *x / n);
+    }
+}
+
+fn pointwise<T: Copy + Mul<Output = T>>(a: &mut [T], b: &[T]) {
+    a.iter_mut().zip(b).for_each(|(x, &y)| *x = *x * y);
+}
+
+/// `c_k = Σ_{i | j = k} a_i b_j`. Both lengths must be the same power of two.
+pub fn or_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    subset_zeta(&mut a, false);
+    subset_zeta(&mut b, false);
+    pointwise(&mut a, &b);
+    subset_zeta(&mut a, true);
+    a
+}
+
+/// `c_k = Σ_{i & j = k} a_i b_j`. Both lengths must be the same power of two.
+pub fn and_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    superset_zeta(&mut a, false);
+    superset_zeta(&mut b, false);
+    pointwise(&mut a, &b);
+    superset_zeta(&mut a, true);
+    a
+}
+
+/// `c_k = Σ_{i ^ j = k} a_i b_j`. Both lengths must be the same power of two.
+pub fn xor_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + From<u32>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    walsh_hadamard(&mut a, false);
+    walsh_hadamard(&mut b, false);
+    pointwise(&mut a, &b);
+    walsh_hadamard(&mut a, true);
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::{and_convolution, or_convolution, xor_convolution};
+    use ac_library::ModInt998244353 as Mint;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn bitwise_random() {
+        let mut rng = Pcg64::seed_from_u64(3401);
+        for k in 0..7 {
+            let n = 1 << k;
+            let a = (0..n).map(|_| rng.gen_range(-50..50)).collect::<Vec<i64>>();
+            let b = (0..n).map(|_| rng.gen_range(-50..50)).collect::<Vec<i64>>();
+            let (mut or, mut and, mut xor) = (vec![0; n], vec![0; n], vec![0; n]);
+            for i in 0..n {
+                for j in 0..n {
+                    or[i | j] += a[i] * b[j];
+                    and[i & j] += a[i] * b[j];
+                    xor[i ^ j] += a[i] * b[j];
+                }
+            }
+            assert_eq!(or_convolution(&a, &b), or);
+            assert_eq!(and_convolution(&a, &b), and);
+            assert_eq!(xor_convolution(&a, &b), xor);
+            let to_mint = |v: &[i64]| v.iter().map(|&x| Mint::new(x)).collect::<Vec<_>>();
+            assert_eq!(xor_convolution(&to_mint(&a), &to_mint(&b)), to_mint(&xor));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use num_complex::Complex64;
+use std::f64::consts::PI;
+
+/// In-place FFT. `a.len()` must be a power of two. The inverse one does not divide by `a.len()`.
+pub fn fft(a: &mut [Complex64], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        // twiddles from the angle directly, to keep the rounding errors small
+        let w = (0..len / 2)
+            .map(|k| Complex64::from_polar(1.0, sign * 2.0 * PI * k as f64 / len as f64))
+            .collect::<Vec<_>>();
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for ((x, y), &wk) in lo.iter_mut().zip(hi.iter_mut()).zip(&w) {
+                let (u, v) = (*x, *y * wk);
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Floating point convolution.
+pub fn convolution_f64(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    // pack a into the real part and b into the imaginary part: (a + ib)^2 = a^2 - b^2 + 2iab
+    let mut f = vec![Complex64::new(0.0, 0.0); n];
+    for (i, &x) in a.iter().enumerate() {
+        f[i].re = x;
+    }
+    for (i, &y) in b.iter().enumerate() {
+        f[i].im = y;
+    }
+    fft(&mut f, false);
+    f.iter_mut().for_each(|x| *x = *x * *x);
+    fft(&mut f, true);
+    f.into_iter()
+        .take(len)
+        .map(|x| x.im / (2 * n) as f64)
+        .collect()
+}
+
+/// Integer convolution by [convolution_f64], exact while every `|c_k|` stays below about `10^14`.
+pub fn convolution_i64_fft(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let to_f64 = |v: &[i64]| v.iter().map(|&x| x as f64).collect::<Vec<_>>();
+    convolution_f64(&to_f64(a), &to_f64(b))
+        .into_iter()
+        .map(|x| x.round() as i64)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::convolution_i64_fft;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn fft_random() {
+        let mut rng = Pcg64::seed_from_u64(3400);
+        for (n, m) in [(0, 3), (1, 1), (5, 7), (300, 1000), (2048, 2048)] {
+            let a = (0..n)
+                .map(|_| rng.gen_range(-1000..1000))
+                .collect::<Vec<i64>>();
+            let b = (0..m)
+                .map(|_| rng.gen_range(-1000..1000))
+                .collect::<Vec<i64>>();
+            let mut expected = vec![0; (n + m).max(1) - 1];
+            for (i, &x) in a.iter().enumerate() {
+                for (j, &y) in b.iter().enumerate() {
+                    expected[i + j] += x * y;
+                }
+            }
+            if n == 0 {
+                expected.clear();
+            }
+            assert_eq!(convolution_i64_fft(&a, &b), expected);
+        }
+    }
+}
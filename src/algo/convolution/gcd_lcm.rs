@@ -0,0 +1,94 @@
+use crate::algo::number_theory::sieve::LinearSieve;
+use std::ops::{Add, Mul, Sub};
+
+/// `a_k <- Σ_{k | j} a_j` over the indices `1..a.len()`. With `inverse`, the Möbius transform.
+/// The index 0 is left untouched.
+pub fn multiple_zeta<T: Copy + Add<Output = T> + Sub<Output = T>>(a: &mut [T], inverse: bool) {
+    let n = a.len().saturating_sub(1);
+    for &p in LinearSieve::new(n).primes() {
+        if inverse {
+            for k in 1..=n / p {
+                a[k] = a[k] - a[k * p];
+            }
+        } else {
+            for k in (1..=n / p).rev() {
+                a[k] = a[k] + a[k * p];
+            }
+        }
+    }
+}
+
+/// `a_k <- Σ_{j | k} a_j` over the indices `1..a.len()`. With `inverse`, the Möbius transform.
+/// The index 0 is left untouched.
+pub fn divisor_zeta<T: Copy + Add<Output = T> + Sub<Output = T>>(a: &mut [T], inverse: bool) {
+    let n = a.len().saturating_sub(1);
+    for &p in LinearSieve::new(n).primes() {
+        if inverse {
+            for k in (1..=n / p).rev() {
+                a[k * p] = a[k * p] - a[k];
+            }
+        } else {
+            for k in 1..=n / p {
+                a[k * p] = a[k * p] + a[k];
+            }
+        }
+    }
+}
+
+/// `c_k = Σ_{gcd(i, j) = k} a_i b_j` for `1 <= k < n`, where both lengths are `n`. `c_0` is `a_0 b_0`.
+pub fn gcd_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    multiple_zeta(&mut a, false);
+    multiple_zeta(&mut b, false);
+    a.iter_mut().zip(&b).for_each(|(x, &y)| *x = *x * y);
+    multiple_zeta(&mut a, true);
+    a
+}
+
+/// `c_k = Σ_{lcm(i, j) = k} a_i b_j` for `1 <= k < n`, where both lengths are `n`. `c_0` is `a_0 b_0`.
+pub fn lcm_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(a.len(), b.len());
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    divisor_zeta(&mut a, false);
+    divisor_zeta(&mut b, false);
+    a.iter_mut().zip(&b).for_each(|(x, &y)| *x = *x * y);
+    divisor_zeta(&mut a, true);
+    a
+}
+
+#[cfg(test)]
+mod test {
+    use super::{gcd_convolution, lcm_convolution};
+    use num_integer::Integer;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn gcd_lcm_random() {
+        let mut rng = Pcg64::seed_from_u64(3402);
+        for n in 1..60 {
+            let a = (0..n).map(|_| rng.gen_range(-20..20)).collect::<Vec<i64>>();
+            let b = (0..n).map(|_| rng.gen_range(-20..20)).collect::<Vec<i64>>();
+            let (mut gcd, mut lcm) = (vec![0; n], vec![0; n]);
+            gcd[0] = a[0] * b[0];
+            lcm[0] = a[0] * b[0];
+            for i in 1..n {
+                for j in 1..n {
+                    gcd[i.gcd(&j)] += a[i] * b[j];
+                    if i.lcm(&j) < n {
+                        lcm[i.lcm(&j)] += a[i] * b[j];
+                    }
+                }
+            }
+            assert_eq!(gcd_convolution(&a, &b), gcd);
+            assert_eq!(lcm_convolution(&a, &b), lcm);
+        }
+    }
+}
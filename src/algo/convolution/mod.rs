@@ -0,0 +1,77 @@
+use ac_library::modint::ModIntBase;
+
+/// Arbitrary modulus convolution via three NTT primes and Garner's algorithm: O(N logN).
+pub mod arbitrary_mod;
+/// AND / OR / XOR convolutions by zeta, Möbius and Walsh-Hadamard transforms: O(N logN).
+pub mod bitwise;
+/// Floating point FFT convolution: O(N logN).
+pub mod fft;
+/// GCD / LCM convolutions by the divisor zeta and Möbius transforms: O(N log logN).
+pub mod gcd_lcm;
+/// Number theoretic transform over NTT-friendly primes: O(N logN).
+pub mod ntt;
+
+/// Schoolbook convolution: O(NM). Faster than the transforms for short inputs.
+pub fn convolution_naive<Z: ModIntBase>(a: &[Z], b: &[Z]) -> Vec<Z> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut c = vec![Z::raw(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            c[i + j] += x * y;
+        }
+    }
+    c
+}
+
+/// Returns `c_k = Σ_{i + j = k} a_i b_j` over any modulus, choosing the fastest method:
+/// schoolbook for short inputs, NTT if the modulus allows, three-prime NTT otherwise.
+pub fn convolve<Z: ModIntBase>(a: &[Z], b: &[Z]) -> Vec<Z> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if a.len().min(b.len()) <= 60 {
+        convolution_naive(a, b)
+    } else if ntt::is_ntt_friendly(Z::modulus(), a.len() + b.len() - 1) {
+        ntt::convolution_ntt(a, b)
+    } else {
+        arbitrary_mod::convolution_arbitrary(a, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{convolution_naive, convolve};
+    use ac_library::modint::{Barrett, DynamicModInt, Id, ModIntBase};
+    use ac_library::{ModInt1000000007, ModInt998244353};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn check<Z: ModIntBase>(rng: &mut Pcg64) {
+        for (n, m) in [(0, 5), (1, 1), (3, 100), (70, 80), (257, 300), (1000, 1)] {
+            let a = (0..n).map(|_| Z::new(rng.gen::<u32>())).collect::<Vec<_>>();
+            let b = (0..m).map(|_| Z::new(rng.gen::<u32>())).collect::<Vec<_>>();
+            assert_eq!(convolve(&a, &b), convolution_naive(&a, &b));
+        }
+    }
+
+    /// A composite modulus, apart from the global one of `ModInt` used by other tests.
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum Composite {}
+
+    impl Id for Composite {
+        fn companion_barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::new(1 << 20);
+            &BARRETT
+        }
+    }
+
+    #[test]
+    fn convolve_dispatch() {
+        let mut rng = Pcg64::seed_from_u64(34);
+        check::<ModInt998244353>(&mut rng);
+        check::<ModInt1000000007>(&mut rng);
+        check::<DynamicModInt<Composite>>(&mut rng);
+    }
+}
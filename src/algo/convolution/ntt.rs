@@ -0,0 +1,114 @@
+use crate::algo::number_theory::prime::{is_prime, primitive_root};
+use ac_library::modint::ModIntBase;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static ROOTS: RefCell<HashMap<u32, u32>> = RefCell::new(HashMap::new());
+}
+
+/// Primitive root of the prime modulus of `Z`, cached per modulus.
+fn root<Z: ModIntBase>() -> Z {
+    let m = Z::modulus();
+    let g = ROOTS.with(|roots| {
+        *roots
+            .borrow_mut()
+            .entry(m)
+            .or_insert_with(|| primitive_root(m as u64) as u32)
+    });
+    Z::raw(g)
+}
+
+/// Whether a cyclic convolution of length `len` is possible modulo `m`,
+/// i.e. `m` is a prime with `2^k | m - 1` for `2^k >= len`.
+pub fn is_ntt_friendly(m: u32, len: usize) -> bool {
+    let len = len.next_power_of_two() as u64;
+    is_prime(m as u64) && (m as u64 - 1) % len == 0
+}
+
+fn butterfly<Z: ModIntBase>(a: &mut [Z], inverse: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    assert!(
+        is_ntt_friendly(Z::modulus(), n),
+        "modulus is not NTT-friendly"
+    );
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let g = if inverse { root::<Z>().inv() } else { root() };
+    let mut len = 2;
+    while len <= n {
+        let w = g.pow((Z::modulus() as u64 - 1) / len as u64);
+        for chunk in a.chunks_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            let mut wk = Z::raw(1);
+            for (x, y) in lo.iter_mut().zip(hi.iter_mut()) {
+                let (u, v) = (*x, *y * wk);
+                *x = u + v;
+                *y = u - v;
+                wk *= w;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// In-place NTT. `a.len()` must be a power of two dividing `modulus - 1`.
+pub fn ntt<Z: ModIntBase>(a: &mut [Z]) {
+    butterfly(a, false);
+}
+
+/// Inverse of [ntt], including the division by `a.len()`.
+pub fn intt<Z: ModIntBase>(a: &mut [Z]) {
+    butterfly(a, true);
+    let inv_n = Z::new(a.len()).inv();
+    a.iter_mut().for_each(|x| *x *= inv_n);
+}
+
+/// Convolution modulo an NTT-friendly prime, see [is_ntt_friendly].
+pub fn convolution_ntt<Z: ModIntBase>(a: &[Z], b: &[Z]) -> Vec<Z> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(n, Z::raw(0));
+    fb.resize(n, Z::raw(0));
+    ntt(&mut fa);
+    ntt(&mut fb);
+    fa.iter_mut().zip(&fb).for_each(|(x, &y)| *x *= y);
+    intt(&mut fa);
+    fa.truncate(len);
+    fa
+}
+
+#[cfg(test)]
+mod test {
+    use super::{intt, is_ntt_friendly, ntt};
+    use ac_library::ModInt998244353 as Mint;
+
+    #[test]
+    fn ntt_inverse() {
+        let a = (0..16).map(|i| Mint::new(i * i + 1)).collect::<Vec<_>>();
+        let mut b = a.clone();
+        ntt(&mut b);
+        assert_eq!(b[0], a.iter().sum());
+        intt(&mut b);
+        assert_eq!(a, b);
+        assert!(is_ntt_friendly(998244353, 1 << 23));
+        assert!(!is_ntt_friendly(998244353, (1 << 23) + 1));
+        assert!(!is_ntt_friendly(1_000_000_007, 4));
+    }
+}
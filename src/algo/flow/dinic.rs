@@ -0,0 +1,87 @@
+use crate::algo::flow::{Edge, MaxFlow, ResidualGraph};
+use num_traits::PrimInt;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+pub struct Dinic<C> {
+    graph: ResidualGraph<C>,
+    level: Vec<usize>,
+    iter: Vec<usize>,
+}
+
+impl<C: PrimInt> Dinic<C> {
+    /// Builds the level graph from `s`, and returns whether `t` is reachable.
+    fn bfs(&mut self, s: usize, t: usize) -> bool {
+        self.level.iter_mut().for_each(|l| *l = usize::MAX);
+        self.level[s] = 0;
+        let mut que = VecDeque::from(vec![s]);
+        while let Some(v) = que.pop_front() {
+            for e in &self.graph.g[v] {
+                if e.cap > C::zero() && self.level[e.to] == usize::MAX {
+                    self.level[e.to] = self.level[v] + 1;
+                    que.push_back(e.to);
+                }
+            }
+        }
+        self.level[t] != usize::MAX
+    }
+
+    /// Sends at most `up` along the level graph from `v` to `t`.
+    fn dfs(&mut self, v: usize, t: usize, up: C) -> C {
+        if v == t {
+            return up;
+        }
+        while self.iter[v] < self.graph.g[v].len() {
+            let i = self.iter[v];
+            let (to, cap) = (self.graph.g[v][i].to, self.graph.g[v][i].cap);
+            if cap > C::zero() && self.level[v] < self.level[to] {
+                let d = self.dfs(to, t, up.min(cap));
+                if d > C::zero() {
+                    self.graph.push(v, i, d);
+                    return d;
+                }
+            }
+            self.iter[v] += 1;
+        }
+        C::zero()
+    }
+}
+
+impl<C: PrimInt> MaxFlow<C> for Dinic<C> {
+    fn new(n: usize) -> Self {
+        Self {
+            graph: ResidualGraph::new(n),
+            level: vec![usize::MAX; n],
+            iter: vec![0; n],
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: C) -> usize {
+        self.graph.add_edge(from, to, cap)
+    }
+
+    fn max_flow(&mut self, s: usize, t: usize) -> C {
+        assert_ne!(s, t);
+        let mut flow = C::zero();
+        // stops once the flow saturates `C`, since no augmenting path can carry more
+        while flow < C::max_value() && self.bfs(s, t) {
+            self.iter.iter_mut().for_each(|i| *i = 0);
+            loop {
+                let f = self.dfs(s, t, C::max_value() - flow);
+                if f == C::zero() {
+                    break;
+                }
+                flow = flow + f;
+            }
+        }
+        flow
+    }
+
+    fn edge(&self, i: usize) -> Edge<C> {
+        self.graph.edge(i)
+    }
+
+    fn min_cut(&self, s: usize) -> Vec<bool> {
+        self.graph.reachable(s)
+    }
+}
@@ -0,0 +1,165 @@
+use crate::algo::flow::{Edge, MaxFlow};
+use num_traits::PrimInt;
+use std::marker::PhantomData;
+
+/// Flow with lower bounds, reduced to a maximum flow on `F` with two extra vertices.
+///
+/// Either [LowerBoundedFlow::feasible_circulation] or [LowerBoundedFlow::max_flow]
+/// should be called at most once, since both add the demand edges.
+#[derive(Debug, Clone)]
+pub struct LowerBoundedFlow<C, F> {
+    cap: PhantomData<C>,
+    n: usize,
+    flow: F,
+    lower: Vec<C>,
+    in_lower: Vec<C>,
+    out_lower: Vec<C>,
+}
+
+impl<C: PrimInt, F: MaxFlow<C>> LowerBoundedFlow<C, F> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            cap: Default::default(),
+            n,
+            flow: F::new(n + 2),
+            lower: vec![],
+            in_lower: vec![C::zero(); n],
+            out_lower: vec![C::zero(); n],
+        }
+    }
+
+    /// Adds an edge `from -> to` whose flow must be in `[lower, upper]`, and returns its index.
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: C, upper: C) -> usize {
+        assert!(C::zero() <= lower && lower <= upper);
+        self.out_lower[from] = self.out_lower[from] + lower;
+        self.in_lower[to] = self.in_lower[to] + lower;
+        self.lower.push(lower);
+        self.flow.add_edge(from, to, upper - lower)
+    }
+
+    /// Returns the `i`-th edge, where `flow` includes its lower bound.
+    pub fn edge(&self, i: usize) -> Edge<C> {
+        let e = self.flow.edge(i);
+        Edge {
+            cap: e.cap + self.lower[i],
+            flow: e.flow + self.lower[i],
+            ..e
+        }
+    }
+
+    /// Routes every excess of lower bounds from the super source to the super sink.
+    fn satisfy_demands(&mut self) -> bool {
+        let (ss, tt) = (self.n, self.n + 1);
+        let mut required = C::zero();
+        for v in 0..self.n {
+            let (i, o) = (self.in_lower[v], self.out_lower[v]);
+            if i > o {
+                self.flow.add_edge(ss, v, i - o);
+                required = required + (i - o);
+            } else if o > i {
+                self.flow.add_edge(v, tt, o - i);
+            }
+        }
+        self.flow.max_flow(ss, tt) == required
+    }
+
+    /// Returns whether there exists a circulation satisfying every bound.
+    /// If so, [LowerBoundedFlow::edge] shows one.
+    pub fn feasible_circulation(&mut self) -> bool {
+        self.satisfy_demands()
+    }
+
+    /// Returns the maximum `s-t` flow satisfying every bound, or `None` if there is no such flow.
+    pub fn max_flow(&mut self, s: usize, t: usize) -> Option<C> {
+        assert_ne!(s, t);
+        self.flow.add_edge(t, s, C::max_value());
+        if !self.satisfy_demands() {
+            return None;
+        }
+        // the reverse of `t -> s` carries the flow found so far, so it is counted again here.
+        Some(self.flow.max_flow(s, t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LowerBoundedFlow;
+    use crate::algo::flow::dinic::Dinic;
+    use crate::algo::flow::push_relabel::PushRelabel;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    /// Tries every assignment of flows, and returns the maximum `0 -> n-1` flow value.
+    fn brute(n: usize, edges: &[(usize, usize, u32, u32)]) -> Option<u32> {
+        let mut best = None;
+        let mut f = edges.iter().map(|e| e.2).collect::<Vec<_>>();
+        loop {
+            let mut balance = vec![0i64; n];
+            for (e, &x) in edges.iter().zip(&f) {
+                balance[e.0] -= x as i64;
+                balance[e.1] += x as i64;
+            }
+            if balance[1..n - 1].iter().all(|&b| b == 0) && balance[n - 1] >= 0 {
+                best = best.max(Some(balance[n - 1] as u32));
+            }
+            match (0..edges.len()).find(|&i| f[i] < edges[i].3) {
+                Some(i) => {
+                    f[i] += 1;
+                    for j in 0..i {
+                        f[j] = edges[j].2;
+                    }
+                }
+                None => return best,
+            }
+        }
+    }
+
+    #[test]
+    fn lower_bound_random() {
+        let mut rng = Pcg64::seed_from_u64(42);
+        for _ in 0..300 {
+            let n = rng.gen_range(2..5);
+            let m = rng.gen_range(0..5);
+            let edges = (0..m)
+                .map(|_| {
+                    let (u, v) = (rng.gen_range(0..n - 1), rng.gen_range(1..n));
+                    let l = rng.gen_range(0..3);
+                    (u, v, l, l + rng.gen_range(0..3))
+                })
+                .collect::<Vec<_>>();
+            let mut dinic = LowerBoundedFlow::<u32, Dinic<u32>>::new(n);
+            let mut pr = LowerBoundedFlow::<u32, PushRelabel<u32>>::new(n);
+            for &(u, v, l, r) in &edges {
+                dinic.add_edge(u, v, l, r);
+                pr.add_edge(u, v, l, r);
+            }
+            let expected = brute(n, &edges);
+            assert_eq!(dinic.max_flow(0, n - 1), expected, "{:?}", edges);
+            assert_eq!(pr.max_flow(0, n - 1), expected, "{:?}", edges);
+            if expected.is_some() {
+                for (i, &(_, _, l, r)) in edges.iter().enumerate() {
+                    let e = dinic.edge(i);
+                    assert!(l <= e.flow && e.flow <= r);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn circulation() {
+        let mut lb = LowerBoundedFlow::<i64, Dinic<i64>>::new(3);
+        lb.add_edge(0, 1, 1, 3);
+        lb.add_edge(1, 2, 2, 2);
+        lb.add_edge(2, 0, 0, 5);
+        assert!(lb.feasible_circulation());
+        assert_eq!(
+            (0..3).map(|i| lb.edge(i).flow).collect::<Vec<_>>(),
+            vec![2, 2, 2]
+        );
+
+        let mut lb = LowerBoundedFlow::<i64, Dinic<i64>>::new(2);
+        lb.add_edge(0, 1, 3, 3);
+        lb.add_edge(1, 0, 0, 2);
+        assert!(!lb.feasible_circulation());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::algo::flow::Edge;
+use num_traits::{PrimInt, Signed};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Clone, Debug)]
+struct CostEdge<C, D> {
+    to: usize,
+    rev: usize,
+    cap: C,
+    cost: D,
+}
+
+/// Capacities are `C`, costs are `D`. The graph must not contain a negative cycle.
+#[derive(Clone, Debug)]
+pub struct MinCostFlow<C, D> {
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<CostEdge<C, D>>>,
+}
+
+impl<C: PrimInt, D: PrimInt + Signed> MinCostFlow<C, D> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            pos: vec![],
+            g: vec![vec![]; n],
+        }
+    }
+
+    /// Adds an edge `from -> to` with capacity `cap` and unit cost `cost`, and returns its index.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: C, cost: D) -> usize {
+        assert!(from < self.g.len() && to < self.g.len());
+        assert!(cap >= C::zero());
+        let m = self.pos.len();
+        let from_id = self.g[from].len();
+        let to_id = self.g[to].len() + usize::from(from == to);
+        self.pos.push((from, from_id));
+        self.g[from].push(CostEdge {
+            to,
+            rev: to_id,
+            cap,
+            cost,
+        });
+        self.g[to].push(CostEdge {
+            to: from,
+            rev: from_id,
+            cap: C::zero(),
+            cost: -cost,
+        });
+        m
+    }
+
+    /// Returns the `i`-th edge and its unit cost.
+    pub fn edge(&self, i: usize) -> (Edge<C>, D) {
+        let (from, id) = self.pos[i];
+        let e = &self.g[from][id];
+        let re = &self.g[e.to][e.rev];
+        let edge = Edge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+        };
+        (edge, e.cost)
+    }
+
+    /// Shortest distances from `s` over the residual edges, by Bellman-Ford.
+    /// Unreachable vertices get 0, since they stay unreachable while augmenting.
+    fn initial_potential(&self, s: usize) -> Vec<D> {
+        let n = self.g.len();
+        let mut dist = vec![None; n];
+        dist[s] = Some(D::zero());
+        for round in 0..n {
+            let mut updated = false;
+            for v in 0..n {
+                let dv = match dist[v] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                for e in self.g[v].iter().filter(|e| e.cap > C::zero()) {
+                    if dist[e.to].map_or(true, |d| dv + e.cost < d) {
+                        dist[e.to] = Some(dv + e.cost);
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+            assert!(round + 1 < n, "negative cycle");
+        }
+        dist.into_iter()
+            .map(|d| d.unwrap_or_else(D::zero))
+            .collect()
+    }
+
+    /// Sends at most `limit` from `s` to `t` with the minimum cost,
+    /// and returns `(amount, cost)`.
+    pub fn flow(&mut self, s: usize, t: usize, limit: C) -> (C, D) {
+        assert_ne!(s, t);
+        let n = self.g.len();
+        let mut potential = self.initial_potential(s);
+        let (mut flow, mut cost) = (C::zero(), D::zero());
+        while flow < limit {
+            // Dijkstra over reduced costs, which are non-negative
+            let mut dist: Vec<Option<D>> = vec![None; n];
+            let mut prev = vec![(usize::MAX, usize::MAX); n];
+            let mut visited = vec![false; n];
+            let mut heap = BinaryHeap::new();
+            dist[s] = Some(D::zero());
+            heap.push(Reverse((D::zero(), s)));
+            while let Some(Reverse((d, v))) = heap.pop() {
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                for (i, e) in self.g[v].iter().enumerate() {
+                    if e.cap == C::zero() {
+                        continue;
+                    }
+                    let nd = d + e.cost + potential[v] - potential[e.to];
+                    if dist[e.to].map_or(true, |d| nd < d) {
+                        dist[e.to] = Some(nd);
+                        prev[e.to] = (v, i);
+                        heap.push(Reverse((nd, e.to)));
+                    }
+                }
+            }
+            if !visited[t] {
+                break;
+            }
+            for v in 0..n {
+                if visited[v] {
+                    potential[v] = potential[v] + dist[v].unwrap();
+                }
+            }
+            let mut d = limit - flow;
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                d = d.min(self.g[u][i].cap);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                let rev = self.g[u][i].rev;
+                self.g[u][i].cap = self.g[u][i].cap - d;
+                self.g[v][rev].cap = self.g[v][rev].cap + d;
+                v = u;
+            }
+            flow = flow + d;
+            cost = cost + D::from(d).unwrap() * (potential[t] - potential[s]);
+        }
+        (flow, cost)
+    }
+
+    /// Sends as much as possible from `s` to `t` with the minimum cost.
+    pub fn min_cost_max_flow(&mut self, s: usize, t: usize) -> (C, D) {
+        self.flow(s, t, C::max_value())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MinCostFlow;
+    use ac_library::MinCostFlowGraph;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn mcf_small() {
+        let mut mcf = MinCostFlow::<u32, i64>::new(4);
+        mcf.add_edge(0, 1, 2, 1);
+        mcf.add_edge(0, 2, 1, 2);
+        mcf.add_edge(1, 2, 1, 1);
+        mcf.add_edge(1, 3, 1, 3);
+        mcf.add_edge(2, 3, 2, 1);
+        assert_eq!(mcf.flow(0, 3, 2), (2, 6));
+        assert_eq!(mcf.min_cost_max_flow(0, 3), (1, 4));
+        assert_eq!(mcf.edge(3).0.flow, 1);
+    }
+
+    /// On a layered graph every path has the same length, so shifting all costs by a constant
+    /// shifts the optimum by `flow * length * shift`. That lets us compare negative costs with ACL.
+    #[test]
+    fn mcf_negative_layered() {
+        let mut rng = Pcg64::seed_from_u64(1234);
+        for _ in 0..200 {
+            let (layers, width) = (rng.gen_range(1..5), rng.gen_range(1..4));
+            let n = layers * width + 2;
+            let (s, t) = (n - 2, n - 1);
+            let shift = 10;
+            let mut mcf = MinCostFlow::<u64, i64>::new(n);
+            let mut acl = MinCostFlowGraph::<i64>::new(n);
+            let mut add = |u: usize, v: usize, cap: i64, cost: i64| {
+                mcf.add_edge(u, v, cap as u64, cost);
+                acl.add_edge(u, v, cap, cost + shift);
+            };
+            for i in 0..width {
+                add(s, i, rng.gen_range(0..5), rng.gen_range(-10..10));
+                add(
+                    (layers - 1) * width + i,
+                    t,
+                    rng.gen_range(0..5),
+                    rng.gen_range(-10..10),
+                );
+            }
+            for l in 0..layers - 1 {
+                for i in 0..width {
+                    for j in 0..width {
+                        if rng.gen_bool(0.7) {
+                            let (u, v) = (l * width + i, (l + 1) * width + j);
+                            add(u, v, rng.gen_range(0..5), rng.gen_range(-10..10));
+                        }
+                    }
+                }
+            }
+            let limit = rng.gen_range(0..10);
+            let (f, c) = mcf.flow(s, t, limit as u64);
+            let (af, ac) = acl.flow(s, t, limit);
+            assert_eq!(f as i64, af);
+            assert_eq!(c + af * (layers as i64 + 1) * shift, ac);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use num_traits::PrimInt;
+use std::collections::VecDeque;
+
+/// Build: O(E), [MaxFlow::max_flow]: O(V^2 E), O(E √V) on unit capacity graphs.
+pub mod dinic;
+/// Feasibility and maximum flow under lower bounds `l <= f(e) <= u`, on top of any [MaxFlow].
+pub mod lower_bound;
+/// Primal-dual with potentials: O(F E logV). Negative costs are allowed (initialized by Bellman-Ford).
+pub mod min_cost_flow;
+/// FIFO push-relabel with gap heuristic, [MaxFlow::max_flow]: O(V^3).
+pub mod push_relabel;
+
+/// A view of an added edge. `flow` is the amount currently sent through it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Edge<C> {
+    pub from: usize,
+    pub to: usize,
+    pub cap: C,
+    pub flow: C,
+}
+
+/// Maximum flow solver over integer capacities.
+pub trait MaxFlow<C: PrimInt> {
+    fn new(n: usize) -> Self;
+    /// Adds an edge `from -> to` with capacity `cap`, and returns its index.
+    fn add_edge(&mut self, from: usize, to: usize, cap: C) -> usize;
+    /// Sends as much as possible from `s` to `t`, and returns the amount.
+    /// Calling it again continues from the current flow.
+    fn max_flow(&mut self, s: usize, t: usize) -> C;
+    fn edge(&self, i: usize) -> Edge<C>;
+    /// Returns `cut[v] = true` iff `v` is reachable from `s` in the residual graph.
+    /// After [MaxFlow::max_flow], those vertices form the `s` side of a minimum cut.
+    fn min_cut(&self, s: usize) -> Vec<bool>;
+}
+
+#[derive(Clone, Debug)]
+struct ResidualEdge<C> {
+    to: usize,
+    rev: usize,
+    cap: C,
+}
+
+/// Adjacency lists shared by the max flow solvers.
+#[derive(Clone, Debug)]
+struct ResidualGraph<C> {
+    pos: Vec<(usize, usize)>,
+    g: Vec<Vec<ResidualEdge<C>>>,
+}
+
+impl<C: PrimInt> ResidualGraph<C> {
+    fn new(n: usize) -> Self {
+        Self {
+            pos: vec![],
+            g: vec![vec![]; n],
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.g.len()
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: C) -> usize {
+        assert!(from < self.len() && to < self.len());
+        assert!(cap >= C::zero());
+        let m = self.pos.len();
+        let from_id = self.g[from].len();
+        let to_id = self.g[to].len() + usize::from(from == to);
+        self.pos.push((from, from_id));
+        self.g[from].push(ResidualEdge {
+            to,
+            rev: to_id,
+            cap,
+        });
+        self.g[to].push(ResidualEdge {
+            to: from,
+            rev: from_id,
+            cap: C::zero(),
+        });
+        m
+    }
+
+    /// Moves `amount` from the `i`-th edge of `v` to its reverse edge.
+    #[inline]
+    fn push(&mut self, v: usize, i: usize, amount: C) {
+        let ResidualEdge { to, rev, .. } = self.g[v][i];
+        self.g[v][i].cap = self.g[v][i].cap - amount;
+        self.g[to][rev].cap = self.g[to][rev].cap + amount;
+    }
+
+    fn edge(&self, i: usize) -> Edge<C> {
+        let (from, id) = self.pos[i];
+        let e = &self.g[from][id];
+        let re = &self.g[e.to][e.rev];
+        Edge {
+            from,
+            to: e.to,
+            cap: e.cap + re.cap,
+            flow: re.cap,
+        }
+    }
+
+    fn reachable(&self, s: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.len()];
+        let mut que = VecDeque::from(vec![s]);
+        visited[s] = true;
+        while let Some(v) = que.pop_front() {
+            for e in &self.g[v] {
+                if e.cap > C::zero() && !visited[e.to] {
+                    visited[e.to] = true;
+                    que.push_back(e.to);
+                }
+            }
+        }
+        visited
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::dinic::Dinic;
+    use super::push_relabel::PushRelabel;
+    use super::MaxFlow;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    /// Checks that the edges form a valid `s-t` flow of value `f`, and the cut has capacity `f`.
+    fn verify<F: MaxFlow<u64>>(solver: &F, n: usize, m: usize, f: u64) {
+        let mut balance = vec![0i64; n];
+        for e in (0..m).map(|i| solver.edge(i)) {
+            assert!(e.flow <= e.cap);
+            balance[e.from] -= e.flow as i64;
+            balance[e.to] += e.flow as i64;
+        }
+        assert_eq!(balance[n - 1], f as i64);
+        assert!(balance[1..n - 1].iter().all(|&b| b == 0));
+        let cut = solver.min_cut(0);
+        assert!(cut[0] && !cut[n - 1]);
+        let cut_capacity: u64 = (0..m)
+            .map(|i| solver.edge(i))
+            .filter(|e| cut[e.from] && !cut[e.to])
+            .map(|e| e.cap)
+            .sum();
+        assert_eq!(cut_capacity, f);
+    }
+
+    #[test]
+    fn flow_small() {
+        let mut dinic = Dinic::<i32>::new(4);
+        dinic.add_edge(0, 1, 2);
+        dinic.add_edge(0, 2, 1);
+        dinic.add_edge(1, 2, 1);
+        dinic.add_edge(1, 3, 1);
+        dinic.add_edge(2, 3, 2);
+        assert_eq!(dinic.max_flow(0, 3), 3);
+        assert_eq!(dinic.min_cut(0), vec![true, false, false, false]);
+    }
+
+    #[test]
+    fn flow_dinic_saturates() {
+        // the total capacity exceeds `u8::MAX`, so the flow stops at it while paths remain
+        let mut dinic = Dinic::<u8>::new(2);
+        dinic.add_edge(0, 1, 200);
+        dinic.add_edge(0, 1, 200);
+        assert_eq!(dinic.max_flow(0, 1), u8::MAX);
+    }
+
+    #[test]
+    fn flow_random_agree() {
+        let mut rng = Pcg64::seed_from_u64(2023);
+        for _ in 0..300 {
+            let n = rng.gen_range(2..9);
+            let m = rng.gen_range(0..20);
+            let mut dinic = Dinic::<u64>::new(n);
+            let mut pr = PushRelabel::<u64>::new(n);
+            for _ in 0..m {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                let c = rng.gen_range(0..10);
+                dinic.add_edge(u, v, c);
+                pr.add_edge(u, v, c);
+            }
+            let f = dinic.max_flow(0, n - 1);
+            assert_eq!(pr.max_flow(0, n - 1), f);
+            verify(&dinic, n, m, f);
+            verify(&pr, n, m, f);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::algo::flow::{Edge, MaxFlow, ResidualGraph};
+use num_traits::PrimInt;
+use std::collections::VecDeque;
+
+/// The total capacity leaving the source must fit in `C`.
+#[derive(Debug, Clone)]
+pub struct PushRelabel<C> {
+    graph: ResidualGraph<C>,
+}
+
+impl<C: PrimInt> MaxFlow<C> for PushRelabel<C> {
+    fn new(n: usize) -> Self {
+        Self {
+            graph: ResidualGraph::new(n),
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: C) -> usize {
+        self.graph.add_edge(from, to, cap)
+    }
+
+    fn max_flow(&mut self, s: usize, t: usize) -> C {
+        assert_ne!(s, t);
+        let n = self.graph.len();
+        let mut height = vec![0; n];
+        let mut count = vec![0usize; 2 * n + 1];
+        let mut excess = vec![C::zero(); n];
+        let mut iter = vec![0; n];
+        let mut active = VecDeque::new();
+        height[s] = n;
+        count[0] = n - 1;
+        count[n] = 1;
+        for i in 0..self.graph.g[s].len() {
+            let (to, cap) = (self.graph.g[s][i].to, self.graph.g[s][i].cap);
+            if cap > C::zero() {
+                self.graph.push(s, i, cap);
+                if to != s && to != t && excess[to] == C::zero() {
+                    active.push_back(to);
+                }
+                excess[to] = excess[to] + cap;
+            }
+        }
+        while let Some(v) = active.pop_front() {
+            // discharge
+            while excess[v] > C::zero() {
+                if iter[v] == self.graph.g[v].len() {
+                    // relabel
+                    let old = height[v];
+                    let new = self.graph.g[v]
+                        .iter()
+                        .filter(|e| e.cap > C::zero())
+                        .map(|e| height[e.to] + 1)
+                        .min()
+                        .unwrap_or(2 * n)
+                        .min(2 * n);
+                    count[old] -= 1;
+                    height[v] = new;
+                    count[new] += 1;
+                    iter[v] = 0;
+                    if count[old] == 0 && old < n {
+                        // gap: no vertex in (old, n) can reach t anymore
+                        for u in 0..n {
+                            if old < height[u] && height[u] < n {
+                                count[height[u]] -= 1;
+                                height[u] = n + 1;
+                                count[n + 1] += 1;
+                            }
+                        }
+                    }
+                    continue;
+                }
+                let i = iter[v];
+                let (to, cap) = (self.graph.g[v][i].to, self.graph.g[v][i].cap);
+                if cap > C::zero() && height[v] == height[to] + 1 {
+                    let d = excess[v].min(cap);
+                    self.graph.push(v, i, d);
+                    excess[v] = excess[v] - d;
+                    if to != s && to != t && excess[to] == C::zero() {
+                        active.push_back(to);
+                    }
+                    excess[to] = excess[to] + d;
+                } else {
+                    iter[v] += 1;
+                }
+            }
+        }
+        // t never pushes, so its excess is exactly the flow added in this call.
+        excess[t]
+    }
+
+    fn edge(&self, i: usize) -> Edge<C> {
+        self.graph.edge(i)
+    }
+
+    fn min_cut(&self, s: usize) -> Vec<bool> {
+        self.graph.reachable(s)
+    }
+}
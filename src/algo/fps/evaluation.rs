@@ -0,0 +1,217 @@
+use crate::algo::fps::{inverses, Fps};
+use ac_library::modint::ModIntBase;
+
+/// Products `Π (x - x_i)` over the segments of the points, indexed like a segment tree.
+struct SubproductTree<Z> {
+    n: usize,
+    tree: Vec<Fps<Z>>,
+}
+
+impl<Z: ModIntBase> SubproductTree<Z> {
+    fn new(xs: &[Z]) -> Self {
+        let n = xs.len();
+        let mut tree = vec![Fps(vec![]); 4 * n.max(1)];
+        fn build<Z: ModIntBase>(tree: &mut [Fps<Z>], node: usize, xs: &[Z]) {
+            if xs.len() == 1 {
+                tree[node] = Fps(vec![-xs[0], Z::raw(1)]);
+                return;
+            }
+            let mid = xs.len() / 2;
+            build(tree, node * 2, &xs[..mid]);
+            build(tree, node * 2 + 1, &xs[mid..]);
+            tree[node] = &tree[node * 2] * &tree[node * 2 + 1];
+        }
+        if n > 0 {
+            build(&mut tree, 1, xs);
+        }
+        Self { n, tree }
+    }
+
+    /// Values of `f` at the leaves below `node`, by taking remainders down the tree.
+    fn evaluate(&self, f: &Fps<Z>, node: usize, len: usize, res: &mut Vec<Z>) {
+        let r = f.div_rem(&self.tree[node]).1;
+        if len == 1 {
+            res.push(r.coef(0));
+            return;
+        }
+        let mid = len / 2;
+        self.evaluate(&r, node * 2, mid, res);
+        self.evaluate(&r, node * 2 + 1, len - mid, res);
+    }
+
+    /// `Σ w_i Π_{j != i} (x - x_j)` over the leaves below `node`.
+    fn combine(&self, w: &[Z], node: usize) -> Fps<Z> {
+        if w.len() == 1 {
+            return Fps(vec![w[0]]);
+        }
+        let mid = w.len() / 2;
+        let l = self.combine(&w[..mid], node * 2);
+        let r = self.combine(&w[mid..], node * 2 + 1);
+        &(&l * &self.tree[node * 2 + 1]) + &(&r * &self.tree[node * 2])
+    }
+}
+
+impl<Z: ModIntBase> Fps<Z> {
+    /// Evaluates the polynomial at a point: O(N).
+    pub fn eval(&self, x: Z) -> Z {
+        self.0.iter().rev().fold(Z::raw(0), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluates the polynomial at every point: O(N log^2 N).
+    pub fn multipoint_eval(&self, xs: &[Z]) -> Vec<Z> {
+        if xs.is_empty() {
+            return vec![];
+        }
+        let tree = SubproductTree::new(xs);
+        let mut res = Vec::with_capacity(xs.len());
+        tree.evaluate(self, 1, tree.n, &mut res);
+        res
+    }
+
+    /// The polynomial of degree `< N` through every `(x_i, y_i)`, for distinct `x_i`: O(N log^2 N).
+    pub fn interpolate(xs: &[Z], ys: &[Z]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        if xs.is_empty() {
+            return Self(vec![]);
+        }
+        let tree = SubproductTree::new(xs);
+        let d = tree.tree[1].derivative().multipoint_eval(xs);
+        let w = ys
+            .iter()
+            .zip(&d)
+            .map(|(&y, &d)| y * d.inv())
+            .collect::<Vec<_>>();
+        tree.combine(&w, 1)
+    }
+
+    /// Returns `f(x + c)`: O(N logN).
+    pub fn taylor_shift(&self, c: Z) -> Self {
+        let n = self.len();
+        if n == 0 {
+            return Self(vec![]);
+        }
+        let inv = inverses::<Z>(n);
+        let mut fact = vec![Z::raw(1); n];
+        let mut inv_fact = vec![Z::raw(1); n];
+        for i in 1..n {
+            fact[i] = fact[i - 1] * Z::new(i);
+            inv_fact[i] = inv_fact[i - 1] * inv[i];
+        }
+        // b_i = 1/i! Σ_j (f_j j!) (c^{j-i} / (j-i)!)
+        let a = Self((0..n).rev().map(|j| self.0[j] * fact[j]).collect());
+        let mut pw = Z::raw(1);
+        let e = inv_fact
+            .iter()
+            .map(|&f| {
+                let x = pw * f;
+                pw *= c;
+                x
+            })
+            .collect();
+        let conv = (&a * &Self(e)).pre(n);
+        Self((0..n).map(|i| conv.0[n - 1 - i] * inv_fact[i]).collect())
+    }
+}
+
+/// Bostan-Mori: `[x^n] P(x) / Q(x)` in O(M logM logn), where `M = deg Q`. Needs `Q_0 != 0`.
+pub fn bostan_mori<Z: ModIntBase>(p: &Fps<Z>, q: &Fps<Z>, mut n: u64) -> Z {
+    assert!(q.coef(0) != Z::raw(0));
+    let (mut p, mut q) = (p.clone(), q.clone());
+    while n > 0 {
+        let q_neg = Fps(q
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| if i % 2 == 0 { x } else { -x })
+            .collect());
+        let u = &p * &q_neg;
+        let v = &q * &q_neg;
+        let parity = (n % 2) as usize;
+        p = Fps(u.0.into_iter().skip(parity).step_by(2).collect());
+        q = Fps(v.0.into_iter().step_by(2).collect());
+        n /= 2;
+    }
+    p.coef(0) * q.0[0].inv()
+}
+
+/// `a_n` of the linear recurrence `a_i = Σ_{j=1}^{d} c_j a_{i-j}`, from `a_0, ..., a_{d-1}`.
+pub fn linear_recurrence_nth<Z: ModIntBase>(a: &[Z], c: &[Z], n: u64) -> Z {
+    let d = c.len();
+    assert_eq!(a.len(), d);
+    let mut q = vec![Z::raw(1)];
+    q.extend(c.iter().map(|&x| -x));
+    let q = Fps(q);
+    let p = (&Fps(a.to_vec()) * &q).pre(d);
+    bostan_mori(&p, &q, n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bostan_mori, linear_recurrence_nth};
+    use crate::algo::fps::Fps;
+    use ac_library::ModInt998244353 as Mint;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn random_vec(rng: &mut Pcg64, n: usize) -> Vec<Mint> {
+        (0..n).map(|_| Mint::new(rng.gen::<u32>())).collect()
+    }
+
+    #[test]
+    fn multipoint_and_interpolate() {
+        let mut rng = Pcg64::seed_from_u64(352);
+        for n in [1, 2, 5, 33, 100] {
+            let f = Fps(random_vec(&mut rng, n));
+            let xs = (0..n as u32 + 3)
+                .map(|i| Mint::new(i * 7 + 1))
+                .collect::<Vec<_>>();
+            let expected = xs.iter().map(|&x| f.eval(x)).collect::<Vec<_>>();
+            assert_eq!(f.multipoint_eval(&xs), expected);
+            let g = Fps::interpolate(&xs[..n], &expected[..n]);
+            assert_eq!(g.pre(n), f);
+        }
+    }
+
+    #[test]
+    fn taylor_shift() {
+        let mut rng = Pcg64::seed_from_u64(353);
+        for n in [0, 1, 4, 50] {
+            let f = Fps(random_vec(&mut rng, n));
+            let c = Mint::new(rng.gen::<u32>());
+            let g = f.taylor_shift(c);
+            for x in [0u32, 1, 5, 12345] {
+                let x = Mint::new(x);
+                assert_eq!(g.eval(x), f.eval(x + c));
+            }
+        }
+    }
+
+    #[test]
+    fn bostan_mori_fibonacci() {
+        let one = Mint::new(1);
+        let mut fib = vec![Mint::new(0), one];
+        for i in 2..100 {
+            fib.push(fib[i - 1] + fib[i - 2]);
+        }
+        for (n, &expected) in fib.iter().enumerate() {
+            assert_eq!(
+                linear_recurrence_nth(&fib[..2], &[one, one], n as u64),
+                expected
+            );
+        }
+        // F_{10^18} mod 998244353, by doubling: F(2k) = F(k)(2F(k+1) - F(k)), F(2k+1) = F(k)^2 + F(k+1)^2
+        let mut fk = (Mint::new(0), one);
+        for b in (0..60).rev() {
+            let (a, b1) = fk;
+            let (c, d) = (a * (b1 * Mint::new(2) - a), a * a + b1 * b1);
+            fk = if 1_000_000_000_000_000_000u64 >> b & 1 == 1 {
+                (d, c + d)
+            } else {
+                (c, d)
+            };
+        }
+        let p = Fps(vec![Mint::new(0), one]);
+        let q = Fps(vec![one, -one, -one]);
+        assert_eq!(bostan_mori(&p, &q, 1_000_000_000_000_000_000), fk.0);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::algo::convolution::convolve;
+use crate::algo::number_theory::modular::ModIntExt;
+use ac_library::modint::ModIntBase;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Multipoint evaluation, interpolation, Taylor shift and Bostan-Mori.
+pub mod evaluation;
+
+/// Formal power series `Σ a_i x^i` over a prime field `Z`.
+///
+/// Multiplication is [convolve], so every Newton iteration below runs in O(N logN)
+/// if the modulus is NTT-friendly, and with the three-prime NTT otherwise.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Fps<Z>(pub Vec<Z>);
+
+impl<Z: ModIntBase> From<Vec<Z>> for Fps<Z> {
+    fn from(a: Vec<Z>) -> Self {
+        Self(a)
+    }
+}
+
+impl<Z: ModIntBase> From<&[Z]> for Fps<Z> {
+    fn from(a: &[Z]) -> Self {
+        Self(a.to_vec())
+    }
+}
+
+impl<Z: ModIntBase> Fps<Z> {
+    pub fn zeros(n: usize) -> Self {
+        Self(vec![Z::raw(0); n])
+    }
+
+    /// `1`, as a series of length 1.
+    pub fn one() -> Self {
+        Self(vec![Z::raw(1)])
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `[x^i] self`, which is 0 beyond the stored coefficients.
+    pub fn coef(&self, i: usize) -> Z {
+        self.0.get(i).copied().unwrap_or(Z::raw(0))
+    }
+
+    /// Returns `self mod x^n`, padded with zeros to length `n`.
+    pub fn pre(&self, n: usize) -> Self {
+        let mut a = self.0[..n.min(self.len())].to_vec();
+        a.resize(n, Z::raw(0));
+        Self(a)
+    }
+
+    /// Removes the trailing zeros.
+    pub fn shrink(mut self) -> Self {
+        while self.0.last() == Some(&Z::raw(0)) {
+            self.0.pop();
+        }
+        self
+    }
+
+    /// Returns `self * x^k`.
+    pub fn shift(&self, k: usize) -> Self {
+        let mut a = vec![Z::raw(0); k];
+        a.extend_from_slice(&self.0);
+        Self(a)
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &x)| x * Z::new(i))
+                .collect(),
+        )
+    }
+
+    /// The antiderivative with constant term 0.
+    pub fn integral(&self) -> Self {
+        let inv = inverses::<Z>(self.len() + 1);
+        let mut a = vec![Z::raw(0)];
+        a.extend(self.0.iter().zip(&inv[1..]).map(|(&x, &i)| x * i));
+        Self(a)
+    }
+
+    /// `1 / self mod x^n`. Needs `self_0 != 0`.
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(self.coef(0) != Z::raw(0), "constant term is 0");
+        let mut g = Self(vec![self.0[0].inv()]);
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            // g <- g (2 - f g)
+            let mut t = -(&self.pre(k) * &g).pre(k);
+            t.0[0] += Z::new(2);
+            g = (&g * &t).pre(k);
+        }
+        g.pre(n)
+    }
+
+    /// `log(self) mod x^n`. Needs `self_0 = 1`.
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.coef(0) == Z::raw(1), "constant term is not 1");
+        if n == 0 {
+            return Self(vec![]);
+        }
+        (&self.derivative().pre(n - 1) * &self.inv(n - 1))
+            .pre(n - 1)
+            .integral()
+    }
+
+    /// `exp(self) mod x^n`. Needs `self_0 = 0`.
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.coef(0) == Z::raw(0), "constant term is not 0");
+        let mut g = Self::one();
+        let mut k = 1;
+        while k < n {
+            k *= 2;
+            // g <- g (1 - log g + f)
+            let mut t = &self.pre(k) - &g.log(k);
+            t.0[0] += Z::raw(1);
+            g = (&g * &t).pre(k);
+        }
+        g.pre(n)
+    }
+
+    /// `self^k mod x^n`.
+    pub fn pow(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Self::one().pre(n);
+        }
+        let i = match self.0.iter().position(|&x| x != Z::raw(0)) {
+            Some(i) => i,
+            None => return Self::zeros(n),
+        };
+        if i as u128 * k as u128 >= n as u128 {
+            return Self::zeros(n);
+        }
+        let shift = i * k as usize;
+        let c = self.0[i];
+        let g = Self(self.0[i..].iter().map(|&x| x * c.inv()).collect());
+        let m = n - shift;
+        let mut h = g.log(m);
+        h *= Z::new(k);
+        let mut h = h.exp(m);
+        h *= c.pow(k);
+        h.shift(shift)
+    }
+
+    /// A square root `mod x^n`, if exists.
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let i = match self.0.iter().position(|&x| x != Z::raw(0)) {
+            Some(i) => i,
+            None => return Some(Self::zeros(n)),
+        };
+        if i % 2 == 1 {
+            return None;
+        }
+        if i / 2 >= n {
+            return Some(Self::zeros(n));
+        }
+        let c = self.0[i];
+        let s = c.sqrt()?;
+        let g = Self(self.0[i..].iter().map(|&x| x * c.inv()).collect());
+        let m = n - i / 2;
+        let inv2 = Z::new(2).inv();
+        let mut h = Self::one();
+        let mut k = 1;
+        while k < m {
+            k *= 2;
+            // h <- (h + g / h) / 2
+            h = &h.pre(k) + &(&g.pre(k) * &h.inv(k)).pre(k);
+            h *= inv2;
+        }
+        let mut h = h.pre(m);
+        h *= s;
+        Some(h.shift(i / 2))
+    }
+
+    /// Polynomial division: returns `(q, r)` with `self = q g + r` and `deg r < deg g`.
+    pub fn div_rem(&self, g: &Self) -> (Self, Self) {
+        let f = self.clone().shrink();
+        let g = g.clone().shrink();
+        assert!(!g.is_empty(), "division by zero polynomial");
+        if f.len() < g.len() {
+            return (Self(vec![]), f);
+        }
+        let q_len = f.len() - g.len() + 1;
+        let rev = |p: &Self| Self(p.0.iter().rev().copied().collect());
+        let q = rev(&(&rev(&f).pre(q_len) * &rev(&g).inv(q_len)).pre(q_len));
+        let r = (&f - &(&g * &q)).pre(g.len() - 1).shrink();
+        (q, r)
+    }
+}
+
+/// `[0, 1, 1/2, ..., 1/(n-1)]`, where `inv[0]` is a dummy.
+pub(crate) fn inverses<Z: ModIntBase>(n: usize) -> Vec<Z> {
+    let p = Z::modulus() as usize;
+    let mut inv = vec![Z::raw(0); n.max(2)];
+    inv[1] = Z::raw(1);
+    for i in 2..n {
+        inv[i] = -inv[p % i] * Z::new(p / i);
+    }
+    inv.truncate(n);
+    inv
+}
+
+impl<Z: ModIntBase> Neg for Fps<Z> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(self.0.into_iter().map(|x| -x).collect())
+    }
+}
+
+impl<Z: ModIntBase> AddAssign<&Fps<Z>> for Fps<Z> {
+    fn add_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.0.resize(rhs.len(), Z::raw(0));
+        }
+        self.0.iter_mut().zip(&rhs.0).for_each(|(x, &y)| *x += y);
+    }
+}
+
+impl<Z: ModIntBase> SubAssign<&Fps<Z>> for Fps<Z> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        if self.len() < rhs.len() {
+            self.0.resize(rhs.len(), Z::raw(0));
+        }
+        self.0.iter_mut().zip(&rhs.0).for_each(|(x, &y)| *x -= y);
+    }
+}
+
+impl<Z: ModIntBase> MulAssign<&Fps<Z>> for Fps<Z> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        self.0 = convolve(&self.0, &rhs.0);
+    }
+}
+
+/// Scalar multiplication.
+impl<Z: ModIntBase> MulAssign<Z> for Fps<Z> {
+    fn mul_assign(&mut self, rhs: Z) {
+        self.0.iter_mut().for_each(|x| *x *= rhs);
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign:ident) => {
+        impl<Z: ModIntBase> $trait<&Fps<Z>> for &Fps<Z> {
+            type Output = Fps<Z>;
+            fn $method(self, rhs: &Fps<Z>) -> Fps<Z> {
+                let mut res = self.clone();
+                res.$assign(rhs);
+                res
+            }
+        }
+
+        impl<Z: ModIntBase> $trait for Fps<Z> {
+            type Output = Fps<Z>;
+            fn $method(mut self, rhs: Fps<Z>) -> Fps<Z> {
+                self.$assign(&rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, add_assign);
+impl_binary_op!(Sub, sub, sub_assign);
+impl_binary_op!(Mul, mul, mul_assign);
+
+#[cfg(test)]
+mod test {
+    use super::Fps;
+    use ac_library::ModInt998244353 as Mint;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn random_fps(rng: &mut Pcg64, n: usize) -> Fps<Mint> {
+        Fps((0..n).map(|_| Mint::new(rng.gen::<u32>())).collect())
+    }
+
+    #[test]
+    fn fps_inv_log_exp() {
+        let mut rng = Pcg64::seed_from_u64(35);
+        for n in [1, 2, 7, 64, 300] {
+            let mut f = random_fps(&mut rng, n);
+            f.0[0] = Mint::new(rng.gen_range(1..100));
+            assert_eq!((&f * &f.inv(n)).pre(n), Fps::one().pre(n));
+
+            f.0[0] = Mint::new(1);
+            let mut g = f.clone();
+            g.0[0] = Mint::new(0);
+            assert_eq!(g.exp(n).log(n), g);
+            assert_eq!(f.log(n).exp(n), f);
+        }
+        // exp(x) = Σ x^i / i!
+        let e = Fps(vec![Mint::new(0), Mint::new(1)]).exp(6);
+        let mut fact = Mint::new(1);
+        for i in 0..6 {
+            assert_eq!(e.0[i] * fact, Mint::new(1));
+            fact *= Mint::new(i + 1);
+        }
+    }
+
+    #[test]
+    fn fps_pow_sqrt() {
+        let mut rng = Pcg64::seed_from_u64(350);
+        for n in [1, 5, 40, 100] {
+            for zeros in [0, 1, 3] {
+                let f = random_fps(&mut rng, n).shift(zeros).pre(n);
+                for k in [0, 1, 2, 5] {
+                    let mut expected = Fps::one().pre(n);
+                    for _ in 0..k {
+                        expected = (&expected * &f).pre(n);
+                    }
+                    assert_eq!(f.pow(k, n), expected);
+                }
+                let sq = (&f * &f).pre(n);
+                let r = sq.sqrt(n).unwrap();
+                assert_eq!((&r * &r).pre(n), sq);
+            }
+        }
+        // 3 is not a quadratic residue, nor is x
+        assert_eq!(Fps(vec![Mint::new(3)]).sqrt(4), None);
+        assert_eq!(Fps(vec![Mint::new(0), Mint::new(1)]).sqrt(4), None);
+    }
+
+    #[test]
+    fn fps_div_rem() {
+        let mut rng = Pcg64::seed_from_u64(351);
+        for (n, m) in [(1, 1), (10, 3), (3, 10), (200, 100), (100, 1)] {
+            let f = random_fps(&mut rng, n).shrink();
+            let g = random_fps(&mut rng, m).shrink();
+            let (q, r) = f.div_rem(&g);
+            assert!(r.len() < g.len());
+            assert_eq!((&(&q * &g) + &r).shrink(), f);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+
+const NIL: usize = usize::MAX;
+
+/// Maximum matching on a general undirected graph.
+#[derive(Debug, Clone)]
+pub struct Blossom {
+    adj: Vec<Vec<usize>>,
+    mate: Vec<usize>,
+    parent: Vec<usize>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+}
+
+impl Blossom {
+    pub fn new(n: usize) -> Self {
+        Self {
+            adj: vec![vec![]; n],
+            mate: vec![NIL; n],
+            parent: vec![NIL; n],
+            base: vec![0; n],
+            used: vec![false; n],
+            in_blossom: vec![false; n],
+        }
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        if u != v {
+            self.adj[u].push(v);
+            self.adj[v].push(u);
+        }
+    }
+
+    /// Lowest common ancestor of `a` and `b` in the alternating tree, in terms of blossom bases.
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.adj.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            if self.mate[a] == NIL {
+                break;
+            }
+            a = self.parent[self.mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+        while self.base[v] != b {
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[self.mate[v]]] = true;
+            self.parent[v] = child;
+            child = self.mate[v];
+            v = self.parent[self.mate[v]];
+        }
+    }
+
+    /// Searches an augmenting path from the free vertex `root`, and returns its other end.
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.adj.len();
+        self.used.iter_mut().for_each(|u| *u = false);
+        self.parent.iter_mut().for_each(|p| *p = NIL);
+        self.base.iter_mut().enumerate().for_each(|(i, b)| *b = i);
+        self.used[root] = true;
+        let mut que = VecDeque::from(vec![root]);
+        while let Some(v) = que.pop_front() {
+            for i in 0..self.adj[v].len() {
+                let to = self.adj[v][i];
+                if self.base[v] == self.base[to] || self.mate[v] == to {
+                    continue;
+                }
+                if to == root || self.mate[to] != NIL && self.parent[self.mate[to]] != NIL {
+                    // odd cycle: contract the blossom
+                    let cur_base = self.lca(v, to);
+                    self.in_blossom.iter_mut().for_each(|b| *b = false);
+                    self.mark_path(v, cur_base, to);
+                    self.mark_path(to, cur_base, v);
+                    for u in 0..n {
+                        if self.in_blossom[self.base[u]] {
+                            self.base[u] = cur_base;
+                            if !self.used[u] {
+                                self.used[u] = true;
+                                que.push_back(u);
+                            }
+                        }
+                    }
+                } else if self.parent[to] == NIL {
+                    self.parent[to] = v;
+                    if self.mate[to] == NIL {
+                        return Some(to);
+                    }
+                    let m = self.mate[to];
+                    self.used[m] = true;
+                    que.push_back(m);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the size of a maximum matching.
+    pub fn max_matching(&mut self) -> usize {
+        for root in 0..self.adj.len() {
+            if self.mate[root] != NIL {
+                continue;
+            }
+            if let Some(mut v) = self.find_path(root) {
+                while v != NIL {
+                    let pv = self.parent[v];
+                    let ppv = self.mate[pv];
+                    self.mate[v] = pv;
+                    self.mate[pv] = v;
+                    v = ppv;
+                }
+            }
+        }
+        self.mate.iter().filter(|&&m| m != NIL).count() / 2
+    }
+
+    pub fn mate(&self, v: usize) -> Option<usize> {
+        Some(self.mate[v]).filter(|&m| m != NIL)
+    }
+
+    /// Returns the matched pairs `(u, v)` with `u < v`.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        (0..self.adj.len())
+            .filter_map(|u| self.mate(u).filter(|&v| u < v).map(|v| (u, v)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Blossom;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    /// Maximum matching by DP over the subsets of matched-or-skipped vertices.
+    fn brute(n: usize, edges: &[(usize, usize)]) -> usize {
+        let mut dp = vec![0usize; 1 << n];
+        for mask in 1usize..1 << n {
+            let v = mask.trailing_zeros() as usize;
+            let rest = mask & !(1 << v);
+            dp[mask] = dp[rest];
+            for &(a, b) in edges {
+                let u = if a == v {
+                    b
+                } else if b == v {
+                    a
+                } else {
+                    continue;
+                };
+                if u != v && rest >> u & 1 == 1 {
+                    dp[mask] = dp[mask].max(dp[rest & !(1 << u)] + 1);
+                }
+            }
+        }
+        dp[(1 << n) - 1]
+    }
+
+    #[test]
+    fn blossom_odd_cycle() {
+        // a triangle with a pendant on each corner
+        let mut b = Blossom::new(6);
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (0, 3), (1, 4), (2, 5)] {
+            b.add_edge(u, v);
+        }
+        assert_eq!(b.max_matching(), 3);
+    }
+
+    #[test]
+    fn blossom_random() {
+        let mut rng = Pcg64::seed_from_u64(314);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..11);
+            let edges = (0..rng.gen_range(0..20))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect::<Vec<_>>();
+            let mut b = Blossom::new(n);
+            for &(u, v) in &edges {
+                b.add_edge(u, v);
+            }
+            let m = b.max_matching();
+            assert_eq!(m, brute(n, &edges), "{:?}", edges);
+            let pairs = b.pairs();
+            assert_eq!(pairs.len(), m);
+            assert!(pairs
+                .iter()
+                .all(|&(u, v)| edges.contains(&(u, v)) || edges.contains(&(v, u))));
+        }
+    }
+}
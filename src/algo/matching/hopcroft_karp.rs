@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+
+/// Bipartite graph with `left` vertices and `right` vertices, both 0-indexed.
+#[derive(Debug, Clone)]
+pub struct HopcroftKarp {
+    adj: Vec<Vec<usize>>,
+    mate_left: Vec<Option<usize>>,
+    mate_right: Vec<Option<usize>>,
+}
+
+impl HopcroftKarp {
+    pub fn new(left: usize, right: usize) -> Self {
+        Self {
+            adj: vec![vec![]; left],
+            mate_left: vec![None; left],
+            mate_right: vec![None; right],
+        }
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(v < self.mate_right.len());
+        self.adj[u].push(v);
+    }
+
+    /// Layers the left vertices by alternating paths from free ones.
+    /// Returns whether some free right vertex is reachable.
+    fn bfs(&self, dist: &mut [usize]) -> bool {
+        let mut que = VecDeque::new();
+        for (u, d) in dist.iter_mut().enumerate() {
+            if self.mate_left[u].is_none() {
+                *d = 0;
+                que.push_back(u);
+            } else {
+                *d = usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(u) = que.pop_front() {
+            for &v in &self.adj[u] {
+                match self.mate_right[v] {
+                    None => found = true,
+                    Some(w) if dist[w] == usize::MAX => {
+                        dist[w] = dist[u] + 1;
+                        que.push_back(w);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        found
+    }
+
+    fn dfs(&mut self, u: usize, dist: &mut [usize], iter: &mut [usize]) -> bool {
+        while iter[u] < self.adj[u].len() {
+            let v = self.adj[u][iter[u]];
+            iter[u] += 1;
+            let ok = match self.mate_right[v] {
+                None => true,
+                Some(w) => dist[w] == dist[u] + 1 && self.dfs(w, dist, iter),
+            };
+            if ok {
+                self.mate_left[u] = Some(v);
+                self.mate_right[v] = Some(u);
+                return true;
+            }
+        }
+        dist[u] = usize::MAX;
+        false
+    }
+
+    /// Returns the size of a maximum matching.
+    pub fn max_matching(&mut self) -> usize {
+        let n = self.adj.len();
+        let mut dist = vec![0; n];
+        while self.bfs(&mut dist) {
+            let mut iter = vec![0; n];
+            for u in 0..n {
+                if self.mate_left[u].is_none() {
+                    self.dfs(u, &mut dist, &mut iter);
+                }
+            }
+        }
+        self.mate_left.iter().filter(|m| m.is_some()).count()
+    }
+
+    pub fn mate_left(&self, u: usize) -> Option<usize> {
+        self.mate_left[u]
+    }
+
+    pub fn mate_right(&self, v: usize) -> Option<usize> {
+        self.mate_right[v]
+    }
+
+    /// Returns the matched pairs `(left, right)`.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        self.mate_left
+            .iter()
+            .enumerate()
+            .filter_map(|(u, m)| m.map(|v| (u, v)))
+            .collect()
+    }
+
+    /// König's theorem: after [HopcroftKarp::max_matching], returns `(left, right)` flags of
+    /// a minimum vertex cover, whose size equals the maximum matching.
+    ///
+    /// Let Z be the vertices reachable from free left vertices by alternating paths,
+    /// then the cover is (L \ Z) ∪ (R ∩ Z).
+    pub fn min_vertex_cover(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut z_left = vec![false; self.adj.len()];
+        let mut z_right = vec![false; self.mate_right.len()];
+        let mut que = VecDeque::new();
+        for (u, z) in z_left.iter_mut().enumerate() {
+            if self.mate_left[u].is_none() {
+                *z = true;
+                que.push_back(u);
+            }
+        }
+        while let Some(u) = que.pop_front() {
+            for &v in &self.adj[u] {
+                if z_right[v] || self.mate_left[u] == Some(v) {
+                    continue;
+                }
+                z_right[v] = true;
+                if let Some(w) = self.mate_right[v] {
+                    if !z_left[w] {
+                        z_left[w] = true;
+                        que.push_back(w);
+                    }
+                }
+            }
+        }
+        (z_left.into_iter().map(|z| !z).collect(), z_right)
+    }
+
+    /// Complement of [HopcroftKarp::min_vertex_cover].
+    pub fn max_independent_set(&self) -> (Vec<bool>, Vec<bool>) {
+        let (l, r) = self.min_vertex_cover();
+        (
+            l.into_iter().map(|c| !c).collect(),
+            r.into_iter().map(|c| !c).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HopcroftKarp;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    /// Maximum matching by DP over the used right vertices.
+    fn brute(left: usize, right: usize, edges: &[(usize, usize)]) -> usize {
+        let mut dp = vec![None; 1 << right];
+        dp[0] = Some(0usize);
+        let mut best = 0;
+        for u in 0..left {
+            let mut next = dp.clone();
+            for (mask, c) in dp.iter().enumerate() {
+                if let &Some(c) = c {
+                    for &(_, v) in edges.iter().filter(|e| e.0 == u) {
+                        if mask >> v & 1 == 0 {
+                            let m = mask | 1 << v;
+                            next[m] = next[m].max(Some(c + 1));
+                            best = best.max(c + 1);
+                        }
+                    }
+                }
+            }
+            dp = next;
+        }
+        best
+    }
+
+    #[test]
+    fn hopcroft_karp_random() {
+        let mut rng = Pcg64::seed_from_u64(7);
+        for _ in 0..300 {
+            let (left, right) = (rng.gen_range(1..7), rng.gen_range(1..7));
+            let edges = (0..rng.gen_range(0..15))
+                .map(|_| (rng.gen_range(0..left), rng.gen_range(0..right)))
+                .collect::<Vec<_>>();
+            let mut hk = HopcroftKarp::new(left, right);
+            for &(u, v) in &edges {
+                hk.add_edge(u, v);
+            }
+            let m = hk.max_matching();
+            assert_eq!(m, brute(left, right, &edges));
+            let pairs = hk.pairs();
+            assert_eq!(pairs.len(), m);
+            assert!(pairs.iter().all(|p| edges.contains(p)));
+            assert!(pairs.iter().all(|&(u, v)| hk.mate_right(v) == Some(u)));
+
+            let (cl, cr) = hk.min_vertex_cover();
+            let size = cl.iter().chain(cr.iter()).filter(|&&c| c).count();
+            assert_eq!(size, m);
+            assert!(edges.iter().all(|&(u, v)| cl[u] || cr[v]));
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use num_traits::{PrimInt, Signed};
+
+/// Returns `(total, assignment)` minimizing `Σ cost[i][assignment[i]]`,
+/// where `assignment` is injective. Needs `cost.len() <= cost[i].len()`.
+pub fn hungarian_min<T: PrimInt + Signed>(cost: &[Vec<T>]) -> (T, Vec<usize>) {
+    let n = cost.len();
+    if n == 0 {
+        return (T::zero(), vec![]);
+    }
+    let m = cost[0].len();
+    assert!(n <= m);
+    // 1-indexed potentials, `p[j]` is the row assigned to column `j` (0 if none).
+    let mut u = vec![T::zero(); n + 1];
+    let mut v = vec![T::zero(); m + 1];
+    let mut p = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut min_v = vec![T::max_value(); m + 1];
+        let mut used = vec![false; m + 1];
+        while p[j0] != 0 {
+            used[j0] = true;
+            let i0 = p[j0];
+            let (mut delta, mut j1) = (T::max_value(), 0);
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < min_v[j] {
+                    min_v[j] = cur;
+                    way[j] = j0;
+                }
+                if min_v[j] < delta {
+                    delta = min_v[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    min_v[j] = min_v[j] - delta;
+                }
+            }
+            j0 = j1;
+        }
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if p[j] != 0 {
+            assignment[p[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).fold(T::zero(), |acc, i| acc + cost[i][assignment[i]]);
+    (total, assignment)
+}
+
+/// Same as [hungarian_min], but maximizes the total.
+pub fn hungarian_max<T: PrimInt + Signed>(cost: &[Vec<T>]) -> (T, Vec<usize>) {
+    let negated = cost
+        .iter()
+        .map(|row| row.iter().map(|&c| -c).collect())
+        .collect::<Vec<_>>();
+    let (total, assignment) = hungarian_min(&negated);
+    (-total, assignment)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hungarian_max, hungarian_min};
+    use itertools::Itertools;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn hungarian_random() {
+        let mut rng = Pcg64::seed_from_u64(99);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..5);
+            let m = rng.gen_range(n.max(1)..6);
+            let cost = (0..n)
+                .map(|_| (0..m).map(|_| rng.gen_range(-20i64..20)).collect())
+                .collect::<Vec<Vec<_>>>();
+            let totals = (0..m)
+                .permutations(n)
+                .map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<i64>())
+                .collect::<Vec<_>>();
+            for (f, expected) in [
+                (
+                    hungarian_min::<i64> as fn(&[Vec<i64>]) -> _,
+                    totals.iter().min(),
+                ),
+                (hungarian_max, totals.iter().max()),
+            ] {
+                let (total, assignment) = f(&cost);
+                assert_eq!(Some(&total), expected);
+                assert!(assignment.iter().all_unique());
+                assert_eq!((0..n).map(|i| cost[i][assignment[i]]).sum::<i64>(), total);
+            }
+        }
+    }
+}
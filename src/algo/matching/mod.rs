@@ -0,0 +1,6 @@
+/// Edmonds' blossom algorithm for general graphs: O(V^3).
+pub mod blossom;
+/// Bipartite maximum matching: O(E √V), with König's minimum vertex cover.
+pub mod hopcroft_karp;
+/// Weighted assignment on a dense `n × m` matrix (`n <= m`): O(n^2 m).
+pub mod hungarian;
@@ -0,0 +1,12 @@
+/// Maximum flow, minimum cost flow, flow with lower bounds and minimum cut.
+pub mod flow;
+/// Bipartite, weighted bipartite and general graph matching.
+pub mod matching;
+/// Suffix array, Z-algorithm, KMP, Manacher, Aho-Corasick and string automata.
+pub mod string;
+/// Sieves, primality, factorization, multiplicative functions, modular equations and combinatorics.
+pub mod number_theory;
+/// NTT, FFT and arbitrary modulus convolutions, and bitwise / GCD / LCM convolutions.
+pub mod convolution;
+/// Formal power series over a prime field.
+pub mod fps;
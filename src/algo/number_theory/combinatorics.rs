@@ -0,0 +1,303 @@
+use crate::algo::fps::Fps;
+use ac_library::modint::ModIntBase;
+
+/// Factorial tables over a prime field `Z`, extended by doubling whenever a larger `n` is requested.
+///
+/// Every `n` passed to the tables must be less than the modulus.
+#[derive(Clone, Debug)]
+pub struct Combinatorics<Z> {
+    fact: Vec<Z>,
+    inv_fact: Vec<Z>,
+    bell: Vec<Z>,
+    partition: Vec<Z>,
+}
+
+impl<Z: ModIntBase> Default for Combinatorics<Z> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Z: ModIntBase> Combinatorics<Z> {
+    pub fn new() -> Self {
+        Self {
+            fact: vec![Z::raw(1)],
+            inv_fact: vec![Z::raw(1)],
+            bell: vec![],
+            partition: vec![],
+        }
+    }
+
+    /// Makes the factorial tables cover `0..=n`.
+    fn ensure(&mut self, n: usize) {
+        let old = self.fact.len();
+        if n < old {
+            return;
+        }
+        assert!((n as u64) < Z::modulus() as u64, "n! is 0 modulo p");
+        let len = (n + 1).next_power_of_two().min(Z::modulus() as usize);
+        for i in old..len {
+            let f = self.fact[i - 1] * Z::new(i);
+            self.fact.push(f);
+        }
+        self.inv_fact.resize(len, Z::raw(0));
+        self.inv_fact[len - 1] = self.fact[len - 1].inv();
+        for i in (old..len - 1).rev() {
+            self.inv_fact[i] = self.inv_fact[i + 1] * Z::new(i + 1);
+        }
+    }
+
+    pub fn fact(&mut self, n: usize) -> Z {
+        self.ensure(n);
+        self.fact[n]
+    }
+
+    pub fn inv_fact(&mut self, n: usize) -> Z {
+        self.ensure(n);
+        self.inv_fact[n]
+    }
+
+    /// `1 / n` for `n >= 1`.
+    pub fn inv(&mut self, n: usize) -> Z {
+        assert!(n > 0);
+        self.ensure(n);
+        self.fact[n - 1] * self.inv_fact[n]
+    }
+
+    /// `nCk`, which is 0 if `k > n`.
+    pub fn binom(&mut self, n: usize, k: usize) -> Z {
+        if k > n {
+            return Z::raw(0);
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// `nPk = n! / (n-k)!`, which is 0 if `k > n`.
+    pub fn perm(&mut self, n: usize, k: usize) -> Z {
+        if k > n {
+            return Z::raw(0);
+        }
+        self.ensure(n);
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// `(Σ k_i)! / Π k_i!`.
+    pub fn multinomial(&mut self, ks: &[usize]) -> Z {
+        let n = ks.iter().sum::<usize>();
+        self.ensure(n);
+        ks.iter()
+            .fold(self.fact[n], |acc, &k| acc * self.inv_fact[k])
+    }
+
+    /// `nCk` by Lucas's theorem, for `n, k` of any size. Fast when the modulus is a small prime,
+    /// since the tables are built up to `p - 1`.
+    pub fn binom_lucas(&mut self, mut n: u64, mut k: u64) -> Z {
+        let p = Z::modulus() as u64;
+        let mut res = Z::raw(1);
+        while k > 0 {
+            let (ni, ki) = ((n % p) as usize, (k % p) as usize);
+            if ki > ni {
+                return Z::raw(0);
+            }
+            res *= self.binom(ni, ki);
+            n /= p;
+            k /= p;
+        }
+        res
+    }
+
+    /// The `n`-th Catalan number `C(2n, n) / (n+1)`.
+    pub fn catalan(&mut self, n: usize) -> Z {
+        self.ensure(2 * n + 1);
+        self.fact[2 * n] * self.inv_fact[n] * self.inv_fact[n + 1]
+    }
+
+    /// Unsigned Stirling numbers of the first kind `[n k]` for `k = 0..=n`,
+    /// i.e. the coefficients of `x (x+1) ... (x+n-1)`: O(N logN).
+    /// The signed ones are `(-1)^{n-k} [n k]`.
+    pub fn stirling1_row(&mut self, n: usize) -> Vec<Z> {
+        self.ensure(n);
+        if n == 0 {
+            return vec![Z::raw(1)];
+        }
+        let half = n / 2;
+        let p = Fps(self.stirling1_row(half));
+        // x (x+1) ... (x+2m-1) = P_m(x) P_m(x+m)
+        let mut res = &p * &p.taylor_shift(Z::new(half));
+        if n % 2 == 1 {
+            res = &res * &Fps(vec![Z::new(n - 1), Z::raw(1)]);
+        }
+        res.pre(n + 1).0
+    }
+
+    /// Stirling numbers of the second kind `{n k}` for `k = 0..=n`: O(N logN).
+    pub fn stirling2_row(&mut self, n: usize) -> Vec<Z> {
+        self.ensure(n);
+        // {n k} = Σ_i (-1)^{k-i} i^n / (i! (k-i)!)
+        let a = Fps((0..=n)
+            .map(|i| Z::new(i).pow(n as u64) * self.inv_fact[i])
+            .collect());
+        let b = Fps((0..=n)
+            .map(|j| {
+                if j % 2 == 0 {
+                    self.inv_fact[j]
+                } else {
+                    -self.inv_fact[j]
+                }
+            })
+            .collect());
+        (&a * &b).pre(n + 1).0
+    }
+
+    /// A single Stirling number of the second kind `{n k}`: O(K log n).
+    pub fn stirling2(&mut self, n: u64, k: usize) -> Z {
+        self.ensure(k);
+        (0..=k)
+            .map(|i| {
+                let t = self.binom(k, i) * Z::new(i).pow(n);
+                if (k - i) % 2 == 0 {
+                    t
+                } else {
+                    -t
+                }
+            })
+            .fold(Z::raw(0), |acc, x| acc + x)
+            * self.inv_fact[k]
+    }
+
+    /// The `n`-th Bell number, from `n! [x^n] exp(e^x - 1)`. The table is cached and doubled.
+    pub fn bell(&mut self, n: usize) -> Z {
+        if n >= self.bell.len() {
+            let len = (n + 1).next_power_of_two();
+            self.ensure(len - 1);
+            let mut f = Fps(self.inv_fact[..len].to_vec());
+            f.0[0] = Z::raw(0);
+            let g = f.exp(len);
+            self.bell = (0..len).map(|i| g.0[i] * self.fact[i]).collect();
+        }
+        self.bell[n]
+    }
+
+    /// The number of partitions of `n`, from Euler's pentagonal theorem. The table is cached and doubled.
+    pub fn partition(&mut self, n: usize) -> Z {
+        if n >= self.partition.len() {
+            let len = (n + 1).next_power_of_two();
+            // Π (1 - x^k) = Σ_k (-1)^k x^{k(3k-1)/2} over all integers k
+            let mut q = Fps::zeros(len);
+            for k in 0.. {
+                let b = k * (3 * k + 1) / 2;
+                let a = b - k;
+                if a >= len {
+                    break;
+                }
+                let sign = if k % 2 == 0 { Z::raw(1) } else { -Z::raw(1) };
+                q.0[a] = sign;
+                if k > 0 && b < len {
+                    q.0[b] = sign;
+                }
+            }
+            self.partition = q.inv(len).0;
+        }
+        self.partition[n]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Combinatorics;
+    use ac_library::modint::{Barrett, DynamicModInt, Id};
+    use ac_library::ModInt998244353 as Mint;
+
+    const N: usize = 60;
+
+    #[test]
+    fn binom_perm_catalan() {
+        let mut c = Combinatorics::<Mint>::new();
+        let mut pascal = vec![vec![Mint::new(0); N + 1]; N + 1];
+        for n in 0..=N {
+            pascal[n][0] = Mint::new(1);
+            for k in 1..=n {
+                pascal[n][k] = pascal[n - 1][k - 1] + pascal[n - 1][k];
+            }
+        }
+        for (n, row) in pascal.iter().enumerate() {
+            for (k, &x) in row.iter().enumerate() {
+                assert_eq!(c.binom(n, k), x);
+                assert_eq!(c.perm(n, k), x * c.fact(k));
+            }
+        }
+        assert_eq!(c.multinomial(&[2, 3, 1]), Mint::new(60));
+        assert_eq!(c.inv(7) * Mint::new(7), Mint::new(1));
+        // on fresh tables, which hold only 0! at first
+        assert_eq!(Combinatorics::<Mint>::new().catalan(0), Mint::new(1));
+        assert_eq!(Combinatorics::<Mint>::new().catalan(1), Mint::new(1));
+        let catalan = [1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862];
+        for (n, &x) in catalan.iter().enumerate() {
+            assert_eq!(c.catalan(n), Mint::new(x));
+        }
+        assert_eq!(c.fact(100_000) * c.inv_fact(100_000), Mint::new(1));
+    }
+
+    #[test]
+    fn stirling_bell_partition() {
+        let mut c = Combinatorics::<Mint>::new();
+        let zero = Mint::new(0);
+        let mut s1 = vec![vec![zero; N + 1]; N + 1];
+        let mut s2 = vec![vec![zero; N + 1]; N + 1];
+        s1[0][0] = Mint::new(1);
+        s2[0][0] = Mint::new(1);
+        for n in 1..=N {
+            for k in 1..=n {
+                s1[n][k] = s1[n - 1][k - 1] + Mint::new(n - 1) * s1[n - 1][k];
+                s2[n][k] = s2[n - 1][k - 1] + Mint::new(k) * s2[n - 1][k];
+            }
+        }
+        let mut partition = vec![vec![zero; N + 1]; N + 1];
+        partition[0] = vec![Mint::new(1); N + 1];
+        for n in 1..=N {
+            for m in 1..=N {
+                partition[n][m] =
+                    partition[n][m - 1] + if m <= n { partition[n - m][m] } else { zero };
+            }
+        }
+        for n in 0..=N {
+            assert_eq!(c.stirling1_row(n), s1[n][..=n]);
+            assert_eq!(c.stirling2_row(n), s2[n][..=n]);
+            for (k, &x) in s2[n][..=n].iter().enumerate() {
+                assert_eq!(c.stirling2(n as u64, k), x);
+            }
+            assert_eq!(c.bell(n), s2[n].iter().sum());
+            assert_eq!(c.partition(n), partition[n][N]);
+        }
+    }
+
+    /// `p = 13`, apart from the global one of `ModInt` used by other tests.
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum Small {}
+
+    impl Id for Small {
+        fn companion_barrett() -> &'static Barrett {
+            static BARRETT: Barrett = Barrett::new(13);
+            &BARRETT
+        }
+    }
+
+    #[test]
+    fn lucas() {
+        let mut c = Combinatorics::<DynamicModInt<Small>>::new();
+        let mut exact = Combinatorics::<Mint>::new();
+        // C(29, 14) < 998244353, so these are exact values
+        for n in 0..30 {
+            for k in 0..=n {
+                let expected = exact.binom(n, k).val() % 13;
+                assert_eq!(c.binom_lucas(n as u64, k as u64).val(), expected);
+            }
+        }
+        // 13-adic digits (7, 0, 0, 0, 0, 5) and (3, 0, 0, 0, 0, 2)
+        let (n, k) = (13u64.pow(5) * 7 + 5, 13u64.pow(5) * 3 + 2);
+        let expected = c.binom(7, 3) * c.binom(5, 2);
+        assert_eq!(c.binom_lucas(n, k), expected);
+        assert_eq!(c.binom_lucas(13, 1).val(), 0);
+    }
+}
@@ -0,0 +1,51 @@
+/// Factorials, binomials, Stirling, Bell, partition and Catalan numbers over a prime field.
+pub mod combinatorics;
+/// CRT, discrete logarithm, modular square roots and linear congruences.
+pub mod modular;
+/// Miller-Rabin, Pollard's rho, divisors and primitive roots for `u64`.
+pub mod prime;
+/// Linear sieve, segmented sieve, and Euler's phi / Möbius tables.
+pub mod sieve;
+
+/// `a + b mod m` for `a, b < m`, without overflow.
+#[inline]
+pub fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    let (s, overflow) = a.overflowing_add(b);
+    if overflow || s >= m {
+        s.wrapping_sub(m)
+    } else {
+        s
+    }
+}
+
+/// `a * b mod m`, without overflow.
+#[inline]
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `⌊√n⌋`, corrected from the floating point estimate.
+pub fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while x as u128 * x as u128 > n as u128 {
+        x -= 1;
+    }
+    while (x as u128 + 1) * (x as u128 + 1) <= n as u128 {
+        x += 1;
+    }
+    x
+}
+
+/// `a^e mod m`.
+pub fn pow_mod(a: u64, mut e: u64, m: u64) -> u64 {
+    let mut a = a % m;
+    let mut res = 1 % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = mul_mod(res, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    res
+}
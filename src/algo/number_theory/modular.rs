@@ -0,0 +1,281 @@
+use crate::algo::number_theory::{add_mod, isqrt, mul_mod, pow_mod};
+use ac_library::modint::ModIntBase;
+use num_integer::Integer;
+use num_traits::{PrimInt, Signed};
+use std::collections::HashMap;
+
+/// Returns `(g, x, y)` with `a x + b y = g = gcd(a, b) >= 0`.
+pub fn ext_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::one(), T::zero());
+    let (mut y0, mut y1) = (T::zero(), T::one());
+    while r1 != T::zero() {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < T::zero() {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Returns `x \in [0, m)` with `a x ≡ 1 (mod m)`, if `gcd(a, m) = 1`.
+pub fn inv_mod(a: i64, m: i64) -> Option<i64> {
+    assert!(m >= 1);
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves `a x ≡ b (mod m)`, and returns `(x0, m')` meaning `x ≡ x0 (mod m')`.
+pub fn linear_congruence(a: i64, b: i64, m: i64) -> Option<(i64, i64)> {
+    assert!(m >= 1);
+    let (a, b) = (a.rem_euclid(m), b.rem_euclid(m));
+    let g = a.gcd(&m);
+    if b % g != 0 {
+        return None;
+    }
+    let m = m / g;
+    let x = (b / g) as i128 * inv_mod(a / g, m).unwrap() as i128 % m as i128;
+    Some((x as i64, m))
+}
+
+/// Generalized Chinese remainder theorem: returns `(x, lcm)` with `x ≡ r_i (mod m_i)` for all i,
+/// or `None` if they are inconsistent. Moduli need not be coprime, but the lcm must fit in `i64`.
+pub fn crt(r: &[i64], m: &[i64]) -> Option<(i64, i64)> {
+    assert_eq!(r.len(), m.len());
+    let (mut r0, mut m0) = (0i128, 1i128);
+    for (&ri, &mi) in r.iter().zip(m) {
+        assert!(mi >= 1);
+        let (ri, mi) = ((ri as i128).rem_euclid(mi as i128), mi as i128);
+        // m0 p ≡ g (mod mi)
+        let (g, p, _) = ext_gcd(m0, mi);
+        if (ri - r0) % g != 0 {
+            return None;
+        }
+        let u = mi / g;
+        let x = (ri - r0) / g % u * p % u;
+        r0 += x * m0;
+        m0 *= u;
+        assert!(m0 <= i64::MAX as i128, "lcm overflows");
+        r0 = r0.rem_euclid(m0);
+    }
+    Some((r0 as i64, m0 as i64))
+}
+
+/// Baby-step giant-step: returns the smallest `x >= 0` with `a^x ≡ b (mod m)`: O(√m).
+/// `a` and `m` need not be coprime.
+pub fn discrete_log(a: u64, b: u64, m: u64) -> Option<u64> {
+    assert!(m >= 1);
+    let (mut a, mut b, mut m) = (a % m, b % m, m);
+    // peel off the common factors of a and m, as a^add * k
+    let (mut k, mut add) = (1 % m, 0);
+    loop {
+        let g = a.gcd(&m);
+        if g == 1 {
+            break;
+        }
+        if b == k {
+            return Some(add);
+        }
+        if b % g != 0 {
+            return None;
+        }
+        b /= g;
+        m /= g;
+        add += 1;
+        k = mul_mod(k, a / g, m);
+        a %= m;
+    }
+    // the smallest `n` with `n^2 >= m`
+    let n = isqrt(m - 1) + 1;
+    let mut baby = HashMap::new();
+    let mut cur = b;
+    for q in 0..=n {
+        baby.insert(cur, q);
+        cur = mul_mod(cur, a, m);
+    }
+    let an = pow_mod(a, n, m);
+    let mut cur = k;
+    for p in 1..=n {
+        cur = mul_mod(cur, an, m);
+        if let Some(&q) = baby.get(&cur) {
+            return Some(n * p - q + add);
+        }
+    }
+    None
+}
+
+/// Tonelli-Shanks: returns the smaller `x` with `x^2 ≡ a (mod p)` for a prime `p`.
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..)
+        .find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)
+        .unwrap();
+    let (mut m, mut c) = (s, pow_mod(z, q, p));
+    let (mut t, mut r) = (pow_mod(a, q, p), pow_mod(a, (q + 1) / 2, p));
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    Some(r.min(p - r))
+}
+
+/// Cipolla: same as [sqrt_mod], in O(log p) regardless of the 2-adic order of `p - 1`.
+pub fn sqrt_mod_cipolla(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    // find t with t^2 - a non-residue, then work in F_p[√w]
+    let (t, w) = (1..)
+        .map(|t| (t, add_mod(mul_mod(t, t, p), p - a, p)))
+        .find(|&(_, w)| pow_mod(w, (p - 1) / 2, p) == p - 1)
+        .unwrap();
+    let mul = |(x1, y1): (u64, u64), (x2, y2): (u64, u64)| {
+        (
+            add_mod(mul_mod(x1, x2, p), mul_mod(mul_mod(y1, y2, p), w, p), p),
+            add_mod(mul_mod(x1, y2, p), mul_mod(y1, x2, p), p),
+        )
+    };
+    let (mut base, mut res) = ((t, 1), (1, 0));
+    let mut e = (p + 1) / 2;
+    while e > 0 {
+        if e & 1 == 1 {
+            res = mul(res, base);
+        }
+        base = mul(base, base);
+        e >>= 1;
+    }
+    Some(res.0.min(p - res.0))
+}
+
+/// The solvers above on a modular integer type. [ModIntExt::sqrt] needs a prime modulus.
+pub trait ModIntExt: ModIntBase {
+    fn sqrt(self) -> Option<Self> {
+        sqrt_mod(self.val() as u64, Self::modulus() as u64).map(Self::new)
+    }
+
+    /// Smallest `x` with `base^x = self`.
+    fn log(self, base: Self) -> Option<u64> {
+        discrete_log(base.val() as u64, self.val() as u64, Self::modulus() as u64)
+    }
+
+    /// Solves `self * x = b`, and returns `(x0, m')` meaning `x ≡ x0 (mod m')`.
+    fn solve_linear(self, b: Self) -> Option<(u32, u32)> {
+        linear_congruence(self.val() as i64, b.val() as i64, Self::modulus() as i64)
+            .map(|(x, m)| (x as u32, m as u32))
+    }
+}
+
+impl<Z: ModIntBase> ModIntExt for Z {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ac_library::{ModInt, ModInt998244353};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn crt_and_linear() {
+        assert_eq!(crt(&[2, 3, 2], &[3, 5, 7]), Some((23, 105)));
+        assert_eq!(crt(&[1, 3], &[4, 6]), Some((9, 12)));
+        assert_eq!(crt(&[1, 2], &[4, 6]), None);
+        assert_eq!(crt(&[], &[]), Some((0, 1)));
+        let big = 1_000_000_000_000_000_003;
+        assert_eq!(crt(&[5, 0], &[big, 2]), Some((big + 5, 2 * big)));
+        let mut rng = Pcg64::seed_from_u64(33);
+        for _ in 0..500 {
+            let m = rng.gen_range(1..40i64);
+            let (a, b) = (rng.gen_range(-50..50), rng.gen_range(-50..50));
+            let sols = (0..m)
+                .filter(|&x| (a * x - b).rem_euclid(m) == 0)
+                .collect::<Vec<_>>();
+            match linear_congruence(a, b, m) {
+                Some((x0, m2)) => {
+                    let expected = (0..m).filter(|&x| x % m2 == x0).collect::<Vec<_>>();
+                    assert_eq!(sols, expected);
+                }
+                None => assert!(sols.is_empty()),
+            }
+            let (m1, m2) = (rng.gen_range(1..30i64), rng.gen_range(1..30i64));
+            let (r1, r2) = (rng.gen_range(-30..30), rng.gen_range(-30..30));
+            let lcm = m1 * m2 / m1.gcd(&m2);
+            let expected = (0..lcm).find(|x| (x - r1) % m1 == 0 && (x - r2) % m2 == 0);
+            assert_eq!(crt(&[r1, r2], &[m1, m2]), expected.map(|x| (x, lcm)));
+        }
+    }
+
+    #[test]
+    fn discrete_log_brute() {
+        for m in 1..50u64 {
+            for a in 0..m {
+                for b in 0..m {
+                    let expected = (0..2 * m).find(|&x| pow_mod(a, x, m) == b % m);
+                    assert_eq!(discrete_log(a, b, m), expected, "{} {} {}", a, b, m);
+                }
+            }
+        }
+        assert_eq!(discrete_log(3, 1, 998244353), Some(0));
+        let x = discrete_log(5, 123456789, 1_000_000_007).unwrap();
+        assert_eq!(pow_mod(5, x, 1_000_000_007), 123456789);
+    }
+
+    #[test]
+    fn sqrt_brute() {
+        for p in [2, 3, 5, 7, 13, 17, 97, 257] {
+            for a in 0..p {
+                let expected = (0..p).find(|&x| x * x % p == a);
+                assert_eq!(sqrt_mod(a, p), expected);
+                assert_eq!(sqrt_mod_cipolla(a, p), expected);
+            }
+        }
+        // the last one is above 2^63, where sums of residues overflow
+        for p in [998244353, 1_000_000_007, (1 << 61) - 1, u64::MAX - 58] {
+            for a in [2, 3, 5, 10, 12345] {
+                for r in [sqrt_mod(a, p), sqrt_mod_cipolla(a, p)]
+                    .into_iter()
+                    .flatten()
+                {
+                    assert_eq!(mul_mod(r, r, p), a);
+                }
+                assert_eq!(sqrt_mod(a, p), sqrt_mod_cipolla(a, p));
+            }
+        }
+    }
+
+    #[test]
+    fn modint_ext() {
+        let x = ModInt998244353::new(123456).pow(2);
+        assert_eq!(x.sqrt().map(|r| r * r), Some(x));
+        assert_eq!(ModInt998244353::new(3).sqrt(), None);
+        let y = ModInt998244353::new(3).pow(1000);
+        assert_eq!(y.log(ModInt998244353::new(3)), Some(1000));
+        ModInt::set_modulus(12);
+        assert_eq!(ModInt::new(4).solve_linear(ModInt::new(8)), Some((2, 3)));
+        assert_eq!(ModInt::new(4).solve_linear(ModInt::new(6)), None);
+    }
+}
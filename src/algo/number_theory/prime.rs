@@ -0,0 +1,214 @@
+use crate::algo::number_theory::{add_mod, mul_mod, pow_mod};
+use num_integer::Integer;
+
+/// Deterministic Miller-Rabin for every `u64`.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    // these bases are enough for n < 2^64
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022]
+        .iter()
+        .all(|&a| {
+            let mut x = pow_mod(a, d, n);
+            if x == 0 || x == 1 || x == n - 1 {
+                return true;
+            }
+            for _ in 1..s {
+                x = mul_mod(x, x, n);
+                if x == n - 1 {
+                    return true;
+                }
+            }
+            false
+        })
+}
+
+/// Returns a non-trivial factor of a composite `n`, by Pollard's rho with Brent's cycle detection.
+fn pollard_rho(n: u64) -> u64 {
+    if n % 2 == 0 {
+        return 2;
+    }
+    let f = |x: u64, c: u64| add_mod(mul_mod(x, x, n), c % n, n);
+    for c in 1.. {
+        let (mut y, mut g, mut q, mut r) = (2, 1, 1, 1);
+        let (mut x, mut ys) = (0, 0);
+        const M: usize = 128;
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y, c);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y;
+                for _ in 0..M.min(r - k) {
+                    y = f(y, c);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = q.gcd(&n);
+                k += M;
+            }
+            r *= 2;
+        }
+        if g == n {
+            // the batch overshot, so retry one by one
+            loop {
+                ys = f(ys, c);
+                g = x.abs_diff(ys).gcd(&n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!()
+}
+
+/// Returns `(p, e)` with `n = Π p^e`, in increasing order of `p`: O(n^{1/4}) expected.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    assert!(n >= 1);
+    let mut primes = vec![];
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let d = pollard_rho(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+    primes.sort_unstable();
+    let mut res: Vec<(u64, u32)> = vec![];
+    for p in primes {
+        match res.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => res.push((p, 1)),
+        }
+    }
+    res
+}
+
+/// Enumerates every divisor from the factorization, in increasing order.
+pub fn divisors(factors: &[(u64, u32)]) -> Vec<u64> {
+    let mut res = vec![1];
+    for &(p, e) in factors {
+        let len = res.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..len {
+                res.push(res[i] * pk);
+            }
+        }
+    }
+    res.sort_unstable();
+    res
+}
+
+/// Returns the smallest primitive root modulo a prime `p`.
+pub fn primitive_root(p: u64) -> u64 {
+    assert!(is_prime(p));
+    if p == 2 {
+        return 1;
+    }
+    let factors = factorize(p - 1);
+    (2..)
+        .find(|&g| {
+            factors
+                .iter()
+                .all(|&(q, _)| pow_mod(g, (p - 1) / q, p) != 1)
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{divisors, factorize, is_prime, primitive_root};
+    use crate::algo::number_theory::pow_mod;
+    use crate::algo::number_theory::sieve::LinearSieve;
+
+    #[test]
+    fn miller_rabin() {
+        let sieve = LinearSieve::new(100000);
+        for n in 0..=100000 {
+            assert_eq!(is_prime(n as u64), sieve.is_prime(n), "{}", n);
+        }
+        assert!(is_prime(998244353));
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime((1 << 61) - 1));
+        assert!(is_prime(18446744073709551557));
+        // strong pseudoprimes to several small bases
+        assert!(!is_prime(3215031751));
+        assert!(!is_prime(3825123056546413051));
+        assert!(!is_prime(1_000_000_007 * 998244353));
+    }
+
+    #[test]
+    fn pollard_rho() {
+        for n in 1..3000u64 {
+            let f = factorize(n);
+            assert_eq!(f.iter().map(|&(p, e)| p.pow(e)).product::<u64>(), n);
+            assert!(f.iter().all(|&(p, _)| is_prime(p)));
+        }
+        let big = [
+            (4295098369, vec![(65537, 2)]),
+            (999999999999999989, vec![(999999999999999989, 1)]),
+            (
+                1_000_000_007 * 998244353,
+                vec![(998244353, 1), (1_000_000_007, 1)],
+            ),
+            (1 << 63, vec![(2, 63)]),
+            // close to 2^64, where `x^2 + c` overflows without a modular add
+            (
+                4294967279 * 4294967291,
+                vec![(4294967279, 1), (4294967291, 1)],
+            ),
+            (
+                18446744073709551615,
+                vec![
+                    (3, 1),
+                    (5, 1),
+                    (17, 1),
+                    (257, 1),
+                    (641, 1),
+                    (65537, 1),
+                    (6700417, 1),
+                ],
+            ),
+        ];
+        for (n, expected) in big {
+            assert_eq!(factorize(n), expected);
+        }
+    }
+
+    #[test]
+    fn divisors_and_root() {
+        for n in 1..500u64 {
+            let expected = (1..=n).filter(|d| n % d == 0).collect::<Vec<_>>();
+            assert_eq!(divisors(&factorize(n)), expected);
+        }
+        assert_eq!(primitive_root(998244353), 3);
+        assert_eq!(primitive_root(1_000_000_007), 5);
+        for p in [2, 3, 5, 7, 11, 13, 17, 19, 23] {
+            let g = primitive_root(p);
+            let mut seen = (1..p).map(|e| pow_mod(g, e, p)).collect::<Vec<_>>();
+            seen.sort_unstable();
+            assert_eq!(seen, (1..p).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::algo::number_theory::isqrt;
+
+/// Linear sieve: build O(N), [LinearSieve::factorize]: O(log N).
+#[derive(Debug, Clone)]
+pub struct LinearSieve {
+    spf: Vec<usize>,
+    primes: Vec<usize>,
+}
+
+impl LinearSieve {
+    /// Sieves `[0, n]`.
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0; n + 1];
+        let mut primes = vec![];
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i;
+                primes.push(i);
+            }
+            for &p in &primes {
+                if p > spf[i] || i * p > n {
+                    break;
+                }
+                spf[i * p] = p;
+            }
+        }
+        Self { spf, primes }
+    }
+
+    /// Upper bound of the sieved range.
+    pub fn max(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    pub fn primes(&self) -> &[usize] {
+        &self.primes
+    }
+
+    /// Smallest prime factor of `n >= 2`.
+    pub fn spf(&self, n: usize) -> usize {
+        assert!(n >= 2);
+        self.spf[n]
+    }
+
+    pub fn is_prime(&self, n: usize) -> bool {
+        n >= 2 && self.spf[n] == n
+    }
+
+    /// Returns `(p, e)` with `n = Π p^e`, in increasing order of `p`.
+    pub fn factorize(&self, mut n: usize) -> Vec<(usize, u32)> {
+        assert!(n >= 1);
+        let mut res: Vec<(usize, u32)> = vec![];
+        while n > 1 {
+            let p = self.spf[n];
+            match res.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => res.push((p, 1)),
+            }
+            n /= p;
+        }
+        res
+    }
+}
+
+/// Primes in `[l, r)`: O((r - l) log log r + √r).
+pub fn segmented_sieve(l: u64, r: u64) -> Vec<u64> {
+    if r <= l {
+        return vec![];
+    }
+    // the smallest `root` with `root^2 >= r`
+    let root = (isqrt(r - 1) + 1) as usize;
+    let small = LinearSieve::new(root);
+    let mut is_prime = vec![true; (r - l) as usize];
+    for &p in small.primes() {
+        let p = p as u64;
+        let start = (p * p).max((l + p - 1) / p * p);
+        for m in (start..r).step_by(p as usize) {
+            is_prime[(m - l) as usize] = false;
+        }
+    }
+    (l..r)
+        .zip(is_prime)
+        .filter(|&(x, ok)| ok && x >= 2)
+        .map(|(x, _)| x)
+        .collect()
+}
+
+/// Euler's totient `φ(i)` for `i \in [0, n]` (`φ(0) = 0`): O(N).
+pub fn phi_table(n: usize) -> Vec<usize> {
+    let sieve = LinearSieve::new(n);
+    let mut phi = vec![0; n + 1];
+    if n >= 1 {
+        phi[1] = 1;
+    }
+    for i in 2..=n {
+        let p = sieve.spf(i);
+        let j = i / p;
+        phi[i] = if j % p == 0 {
+            phi[j] * p
+        } else {
+            phi[j] * (p - 1)
+        };
+    }
+    phi
+}
+
+/// Möbius function `μ(i)` for `i \in [0, n]` (`μ(0) = 0`): O(N).
+pub fn mobius_table(n: usize) -> Vec<i8> {
+    let sieve = LinearSieve::new(n);
+    let mut mu = vec![0; n + 1];
+    if n >= 1 {
+        mu[1] = 1;
+    }
+    for i in 2..=n {
+        let p = sieve.spf(i);
+        let j = i / p;
+        mu[i] = if j % p == 0 { 0 } else { -mu[j] };
+    }
+    mu
+}
+
+#[cfg(test)]
+mod test {
+    use super::{mobius_table, phi_table, segmented_sieve, LinearSieve};
+    use crate::algo::number_theory::isqrt;
+    use crate::algo::number_theory::prime::is_prime;
+
+    fn naive_factorize(mut n: usize) -> Vec<(usize, u32)> {
+        let mut res = vec![];
+        let mut p = 2;
+        while n > 1 {
+            let mut e = 0;
+            while n % p == 0 {
+                n /= p;
+                e += 1;
+            }
+            if e > 0 {
+                res.push((p, e));
+            }
+            p += 1;
+        }
+        res
+    }
+
+    #[test]
+    fn linear_sieve() {
+        let n = 1000;
+        let sieve = LinearSieve::new(n);
+        let primes = (2..=n)
+            .filter(|&i| (2..i).all(|d| i % d != 0))
+            .collect::<Vec<_>>();
+        assert_eq!(sieve.primes(), primes.as_slice());
+        for i in 1..=n {
+            assert_eq!(sieve.factorize(i), naive_factorize(i));
+        }
+        assert_eq!(
+            segmented_sieve(900, 1001),
+            primes
+                .iter()
+                .map(|&p| p as u64)
+                .filter(|&p| p >= 900)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(segmented_sieve(0, 10), vec![2, 3, 5, 7]);
+        let l = 1_000_000_000_000;
+        assert_eq!(
+            segmented_sieve(l, l + 1000),
+            (l..l + 1000).filter(|&n| is_prime(n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn integer_sqrt() {
+        for n in 0..10000u64 {
+            let x = isqrt(n);
+            assert!(x * x <= n && n < (x + 1) * (x + 1));
+        }
+        for x in [94906265, 94906266, 1 << 31, u32::MAX as u64] {
+            assert_eq!(isqrt(x * x), x);
+            assert_eq!(isqrt(x * x - 1), x - 1);
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn phi_mobius() {
+        let n = 500;
+        let phi = phi_table(n);
+        let mu = mobius_table(n);
+        let sieve = LinearSieve::new(n);
+        for i in 1..=n {
+            let gcd = |mut a: usize, mut b: usize| {
+                while b > 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            };
+            assert_eq!(phi[i], (1..=i).filter(|&j| gcd(i, j) == 1).count());
+            let f = sieve.factorize(i);
+            let expected = if f.iter().any(|&(_, e)| e > 1) {
+                0
+            } else if f.len() % 2 == 0 {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(mu[i], expected);
+        }
+    }
+}
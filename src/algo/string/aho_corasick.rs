@@ -0,0 +1,281 @@
+use crate::algebra::Monoid;
+use std::collections::{BTreeMap, VecDeque};
+use std::marker::PhantomData;
+
+/// Trie of patterns over symbols `C`, with failure links.
+///
+/// Each pattern carries a weight `E`. After [AhoCorasick::build], [AhoCorasick::value] of a state
+/// is the `T`-fold of the weights of every pattern that is a suffix of the state,
+/// i.e. of every pattern ending at the current position while scanning a text.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<C, E, T> {
+    alg: PhantomData<T>,
+    children: Vec<BTreeMap<C, usize>>,
+    fail: Vec<usize>,
+    /// nearest proper suffix state which is the end of some pattern
+    output: Vec<Option<usize>>,
+    patterns: Vec<Vec<usize>>,
+    pattern_count: usize,
+    value: Vec<E>,
+    /// index of each symbol appearing in the patterns
+    alphabet: BTreeMap<C, usize>,
+    /// `goto[v * alphabet.len() + a]`, the transition from `v` by the `a`-th symbol
+    goto: Vec<usize>,
+    built: bool,
+}
+
+impl<C: Ord + Clone, E: Clone, T: Monoid<E>> Default for AhoCorasick<C, E, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ord + Clone, E: Clone, T: Monoid<E>> AhoCorasick<C, E, T> {
+    pub const ROOT: usize = 0;
+
+    pub fn new() -> Self {
+        Self {
+            alg: Default::default(),
+            children: vec![BTreeMap::new()],
+            fail: vec![0],
+            output: vec![None],
+            patterns: vec![vec![]],
+            pattern_count: 0,
+            value: vec![T::id()],
+            alphabet: BTreeMap::new(),
+            goto: vec![],
+            built: false,
+        }
+    }
+
+    /// Number of states, including the root.
+    pub fn num_states(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn has_no_patterns(&self) -> bool {
+        self.pattern_count == 0
+    }
+
+    /// Adds a pattern with its weight, and returns the pattern id (0-indexed, in insertion order).
+    pub fn insert(&mut self, pattern: &[C], weight: E) -> usize {
+        assert!(!self.built, "insert after build");
+        let mut v = Self::ROOT;
+        for c in pattern {
+            v = match self.children[v].get(c) {
+                Some(&u) => u,
+                None => {
+                    let u = self.num_states();
+                    self.children.push(BTreeMap::new());
+                    self.fail.push(0);
+                    self.output.push(None);
+                    self.patterns.push(vec![]);
+                    self.value.push(T::id());
+                    self.children[v].insert(c.clone(), u);
+                    u
+                }
+            };
+        }
+        let id = self.pattern_count;
+        self.pattern_count += 1;
+        self.patterns[v].push(id);
+        self.value[v] = T::op(&self.value[v], &weight);
+        id
+    }
+
+    /// Computes the failure links and the transition table, and folds the weights along the links:
+    /// O(states * alphabet).
+    pub fn build(&mut self) {
+        assert!(!self.built, "build twice");
+        self.built = true;
+        self.alphabet = self
+            .children
+            .iter()
+            .flat_map(|m| m.keys().map(|c| (c.clone(), 0)))
+            .collect();
+        for (i, a) in self.alphabet.values_mut().enumerate() {
+            *a = i;
+        }
+        let sigma = self.alphabet.len();
+        self.goto = vec![Self::ROOT; self.num_states() * sigma];
+        let mut que = VecDeque::from(vec![Self::ROOT]);
+        while let Some(v) = que.pop_front() {
+            // states are visited in BFS order, so the row of `fail[v]` is already complete
+            for a in 0..sigma {
+                self.goto[v * sigma + a] = self.goto[self.fail[v] * sigma + a];
+            }
+            let children = self.children[v]
+                .iter()
+                .map(|(c, &u)| (self.alphabet[c], u))
+                .collect::<Vec<_>>();
+            for (a, u) in children {
+                let f = if v == Self::ROOT {
+                    Self::ROOT
+                } else {
+                    self.goto[self.fail[v] * sigma + a]
+                };
+                self.goto[v * sigma + a] = u;
+                self.fail[u] = f;
+                self.output[u] = if self.patterns[f].is_empty() {
+                    self.output[f]
+                } else {
+                    Some(f)
+                };
+                self.value[u] = T::op(&self.value[f], &self.value[u]);
+                que.push_back(u);
+            }
+        }
+    }
+
+    /// Index of `c` among the symbols appearing in the patterns, for [AhoCorasick::goto].
+    pub fn symbol(&self, c: &C) -> Option<usize> {
+        self.alphabet.get(c).copied()
+    }
+
+    /// Transition by the `a`-th symbol: O(1).
+    pub fn goto(&self, state: usize, a: usize) -> usize {
+        assert!(self.built, "query before build");
+        self.goto[state * self.alphabet.len() + a]
+    }
+
+    /// Transition of the automaton: O(log alphabet) to look up `c`.
+    /// Symbols not in any pattern lead to the root.
+    pub fn next(&self, state: usize, c: &C) -> usize {
+        match self.symbol(c) {
+            Some(a) => self.goto(state, a),
+            None => {
+                assert!(self.built, "query before build");
+                Self::ROOT
+            }
+        }
+    }
+
+    /// Fold of the weights of the patterns which are suffixes of `state`.
+    pub fn value(&self, state: usize) -> &E {
+        assert!(self.built, "query before build");
+        &self.value[state]
+    }
+
+    /// Returns the states after reading each prefix of `text`, starting from the root.
+    pub fn states<'a>(&'a self, text: &'a [C]) -> impl Iterator<Item = usize> + 'a {
+        assert!(self.built, "query before build");
+        text.iter().scan(Self::ROOT, move |state, c| {
+            *state = self.next(*state, c);
+            Some(*state)
+        })
+    }
+
+    /// Returns `(end, id)` for each occurrence of the pattern `id` as `text[end - len..end]`.
+    pub fn matches(&self, text: &[C]) -> Vec<(usize, usize)> {
+        let mut res = vec![];
+        for (i, state) in self.states(text).enumerate() {
+            let mut v = Some(state);
+            while let Some(u) = v {
+                res.extend(self.patterns[u].iter().map(|&id| (i + 1, id)));
+                v = self.output[u];
+            }
+        }
+        res
+    }
+
+    /// `T`-fold of the weights of all occurrences in `text`.
+    pub fn fold_matches(&self, text: &[C]) -> E {
+        self.states(text)
+            .fold(T::id(), |acc, state| T::op(&acc, &self.value[state]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AhoCorasick;
+    use crate::algebra::typical::{AdditiveStruct, MaxMonoid};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn random_string(rng: &mut Pcg64, max_len: usize) -> Vec<u8> {
+        (0..rng.gen_range(0..max_len))
+            .map(|_| b'a' + rng.gen_range(0..3))
+            .collect()
+    }
+
+    #[test]
+    fn aho_corasick_matches() {
+        let mut rng = Pcg64::seed_from_u64(30);
+        for _ in 0..200 {
+            let patterns = (0..rng.gen_range(1..5))
+                .map(|_| random_string(&mut rng, 4))
+                .filter(|p| !p.is_empty())
+                .collect::<Vec<_>>();
+            let text = random_string(&mut rng, 30);
+            let mut ac = AhoCorasick::<u8, i64, AdditiveStruct>::new();
+            let mut best = AhoCorasick::<u8, i64, MaxMonoid>::new();
+            for (id, p) in patterns.iter().enumerate() {
+                assert_eq!(ac.insert(p, 1), id);
+                best.insert(p, id as i64);
+            }
+            ac.build();
+            best.build();
+
+            let mut expected = vec![];
+            for end in 1..=text.len() {
+                for (id, p) in patterns.iter().enumerate() {
+                    if text[..end].ends_with(p) {
+                        expected.push((end, id));
+                    }
+                }
+            }
+            let mut actual = ac.matches(&text);
+            actual.sort();
+            assert_eq!(actual, expected);
+            assert_eq!(ac.fold_matches(&text), expected.len() as i64);
+            for (i, state) in best.states(&text).enumerate() {
+                let max_id = expected.iter().filter(|m| m.0 == i + 1).map(|m| m.1 as i64);
+                assert_eq!(*best.value(state), max_id.max().unwrap_or(i64::MIN));
+            }
+        }
+    }
+
+    #[test]
+    fn aho_corasick_avoiding_dp() {
+        // count strings of length 8 over {a, b, c} containing none of the patterns
+        let patterns: [&[u8]; 3] = [b"ab", b"bca", b"cc"];
+        let mut ac = AhoCorasick::<u8, u32, AdditiveStruct>::new();
+        assert!(ac.has_no_patterns());
+        for p in patterns {
+            ac.insert(p, 1);
+        }
+        assert!(!ac.has_no_patterns());
+        ac.build();
+        let mut dp = vec![0u64; ac.num_states()];
+        dp[AhoCorasick::<u8, u32, AdditiveStruct>::ROOT] = 1;
+        for _ in 0..8 {
+            let mut next = vec![0u64; ac.num_states()];
+            for (v, &x) in dp.iter().enumerate() {
+                for c in b"abc" {
+                    let u = ac.goto(v, ac.symbol(c).unwrap());
+                    if *ac.value(u) == 0 {
+                        next[u] += x;
+                    }
+                }
+            }
+            dp = next;
+        }
+        let brute = (0..3usize.pow(8))
+            .map(|mut x| {
+                (0..8)
+                    .map(|_| {
+                        let c = b'a' + (x % 3) as u8;
+                        x /= 3;
+                        c
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|s| {
+                patterns
+                    .iter()
+                    .all(|p| !s.windows(p.len()).any(|w| w == *p))
+            })
+            .count();
+        assert_eq!(dp.iter().sum::<u64>(), brute as u64);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::algo::string::Transition;
+
+#[derive(Debug, Clone)]
+struct Node<M> {
+    /// -1 for the imaginary root
+    len: isize,
+    link: usize,
+    next: M,
+    count: usize,
+}
+
+/// Palindromic tree: a node for each distinct palindromic substring, built online.
+///
+/// Node 0 is the imaginary root of length -1, node 1 is the empty palindrome.
+#[derive(Debug, Clone)]
+pub struct Eertree<C, M> {
+    s: Vec<C>,
+    nodes: Vec<Node<M>>,
+    last: usize,
+    propagated: Option<Vec<usize>>,
+}
+
+impl<C: Clone + Eq, M: Transition<C>> Default for Eertree<C, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clone + Eq, M: Transition<C>> From<&[C]> for Eertree<C, M> {
+    fn from(s: &[C]) -> Self {
+        let mut tree = Self::new();
+        for c in s {
+            tree.push(c.clone());
+        }
+        tree
+    }
+}
+
+impl<C: Clone + Eq, M: Transition<C>> Eertree<C, M> {
+    pub fn new() -> Self {
+        let root = |len| Node {
+            len,
+            link: 0,
+            next: M::default(),
+            count: 0,
+        };
+        Self {
+            s: vec![],
+            nodes: vec![root(-1), root(0)],
+            last: 1,
+            propagated: None,
+        }
+    }
+
+    /// Number of nodes, including the two roots.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether only the two roots exist, i.e. there is no palindrome yet.
+    pub fn is_trivial(&self) -> bool {
+        self.num_nodes() == 2
+    }
+
+    /// Follows suffix links from `v` until `xPx` fits at the end, where `x` is the last symbol.
+    fn find(&self, mut v: usize) -> usize {
+        let i = self.s.len() as isize - 1;
+        loop {
+            let j = i - self.nodes[v].len - 1;
+            if j >= 0 && self.s[j as usize] == self.s[i as usize] {
+                return v;
+            }
+            v = self.nodes[v].link;
+        }
+    }
+
+    /// Appends `c`, and returns the node of the longest palindromic suffix.
+    pub fn push(&mut self, c: C) -> usize {
+        self.propagated = None;
+        self.s.push(c.clone());
+        let cur = self.find(self.last);
+        if let Some(v) = self.nodes[cur].next.get(&c) {
+            self.nodes[v].count += 1;
+            self.last = v;
+            return v;
+        }
+        let len = self.nodes[cur].len + 2;
+        let link = if len == 1 {
+            1
+        } else {
+            let w = self.find(self.nodes[cur].link);
+            self.nodes[w].next.get(&c).unwrap()
+        };
+        let v = self.nodes.len();
+        self.nodes.push(Node {
+            len,
+            link,
+            next: M::default(),
+            count: 1,
+        });
+        self.nodes[cur].next.set(c, v);
+        self.last = v;
+        v
+    }
+
+    /// Number of distinct non-empty palindromic substrings.
+    pub fn distinct_palindromes(&self) -> usize {
+        self.num_nodes() - 2
+    }
+
+    /// Length of the palindrome of node `v`.
+    pub fn palindrome_len(&self, v: usize) -> usize {
+        self.nodes[v].len.max(0) as usize
+    }
+
+    pub fn link(&self, v: usize) -> usize {
+        self.nodes[v].link
+    }
+
+    /// Number of occurrences of each node's palindrome in the whole string.
+    /// Computed lazily, and cached until the next [Eertree::push].
+    pub fn counts(&mut self) -> &[usize] {
+        if self.propagated.is_none() {
+            let mut cnt = self.nodes.iter().map(|n| n.count).collect::<Vec<_>>();
+            // links always point to older nodes
+            for v in (2..self.num_nodes()).rev() {
+                cnt[self.nodes[v].link] += cnt[v];
+            }
+            self.propagated = Some(cnt);
+        }
+        self.propagated.as_ref().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Eertree;
+    use crate::algo::string::ArrayTransition;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn eertree_random() {
+        let mut rng = Pcg64::seed_from_u64(131);
+        for _ in 0..200 {
+            let s = (0..rng.gen_range(0..25))
+                .map(|_| rng.gen_range(0..3))
+                .collect::<Vec<u8>>();
+            let mut expected = BTreeMap::new();
+            for i in 0..s.len() {
+                for j in i + 1..=s.len() {
+                    if s[i..j].iter().eq(s[i..j].iter().rev()) {
+                        *expected.entry(&s[i..j]).or_insert(0usize) += 1;
+                    }
+                }
+            }
+            let mut map = Eertree::<u8, BTreeMap<u8, usize>>::from(s.as_slice());
+            let mut arr = Eertree::<u8, ArrayTransition<3>>::from(s.as_slice());
+            assert_eq!(map.distinct_palindromes(), expected.len());
+            assert_eq!(arr.distinct_palindromes(), expected.len());
+            assert_eq!(map.is_trivial(), s.is_empty());
+            let total = expected.values().sum::<usize>();
+            assert_eq!(map.counts()[2..].iter().sum::<usize>(), total);
+            let counts = arr.counts().to_vec();
+            let mut lens = (2..arr.num_nodes())
+                .map(|v| (arr.palindrome_len(v), counts[v]))
+                .collect::<Vec<_>>();
+            let mut expected_lens = expected
+                .iter()
+                .map(|(p, &c)| (p.len(), c))
+                .collect::<Vec<_>>();
+            lens.sort();
+            expected_lens.sort();
+            assert_eq!(lens, expected_lens);
+        }
+    }
+}
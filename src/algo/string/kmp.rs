@@ -0,0 +1,78 @@
+/// Returns `pi[i]`: the length of the longest proper border of `s[..=i]`.
+pub fn prefix_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+    for i in 1..s.len() {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+    pi
+}
+
+/// Finds every occurrence of a fixed pattern.
+#[derive(Debug, Clone)]
+pub struct KmpMatcher<T> {
+    pattern: Vec<T>,
+    pi: Vec<usize>,
+}
+
+impl<T: Eq + Clone> KmpMatcher<T> {
+    pub fn new(pattern: &[T]) -> Self {
+        Self {
+            pattern: pattern.to_vec(),
+            pi: prefix_function(pattern),
+        }
+    }
+
+    /// Returns the starting positions of the occurrences in `text`, in increasing order.
+    pub fn find_all(&self, text: &[T]) -> Vec<usize> {
+        let m = self.pattern.len();
+        if m == 0 {
+            return (0..=text.len()).collect();
+        }
+        let mut res = vec![];
+        let mut k = 0;
+        for (i, c) in text.iter().enumerate() {
+            while k > 0 && (k == m || *c != self.pattern[k]) {
+                k = self.pi[k - 1];
+            }
+            if *c == self.pattern[k] {
+                k += 1;
+            }
+            if k == m {
+                res.push(i + 1 - m);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{prefix_function, KmpMatcher};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn kmp_random() {
+        assert_eq!(prefix_function(b"aabaaab"), vec![0, 1, 0, 1, 2, 2, 3]);
+        let mut rng = Pcg64::seed_from_u64(1);
+        for _ in 0..300 {
+            let gen = |rng: &mut Pcg64, n: usize| {
+                (0..rng.gen_range(0..n))
+                    .map(|_| b'a' + rng.gen_range(0..2))
+                    .collect::<Vec<_>>()
+            };
+            let (text, pattern) = (gen(&mut rng, 30), gen(&mut rng, 4));
+            let expected = (0..=text.len())
+                .filter(|&i| text[i..].starts_with(&pattern))
+                .collect::<Vec<_>>();
+            assert_eq!(KmpMatcher::new(&pattern).find_all(&text), expected);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/// Returns the length of the longest palindrome at each of the `2N - 1` centers:
+/// `res[2i]` is centered at `s[i]`, and `res[2i + 1]` is centered between `s[i]` and `s[i + 1]`.
+pub fn manacher<T: Eq>(s: &[T]) -> Vec<usize> {
+    if s.is_empty() {
+        return vec![];
+    }
+    // interleave separators so that every palindrome has odd length
+    let t = s
+        .iter()
+        .flat_map(|c| [None, Some(c)])
+        .skip(1)
+        .collect::<Vec<_>>();
+    let n = t.len();
+    let mut rad = vec![0; n];
+    let (mut i, mut j) = (0, 0);
+    while i < n {
+        while j <= i && i + j < n && t[i - j] == t[i + j] {
+            j += 1;
+        }
+        rad[i] = j;
+        let mut k = 1;
+        while k <= i && k + rad[i - k] < j {
+            rad[i + k] = rad[i - k];
+            k += 1;
+        }
+        i += k;
+        j -= k;
+    }
+    // count only the original characters inside `t[i - r + 1..i + r]`
+    rad.iter()
+        .enumerate()
+        .map(|(i, &r)| r - (r + i + 1) % 2)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::manacher;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn is_palindrome(s: &[u8]) -> bool {
+        s.iter().eq(s.iter().rev())
+    }
+
+    #[test]
+    fn manacher_random() {
+        assert_eq!(manacher(b"abaab"), vec![1, 0, 3, 0, 1, 4, 1, 0, 1]);
+        let mut rng = Pcg64::seed_from_u64(2);
+        for _ in 0..300 {
+            let s = (0..rng.gen_range(0..20))
+                .map(|_| b'a' + rng.gen_range(0..2))
+                .collect::<Vec<_>>();
+            let n = s.len();
+            // a palindrome s[lo..hi] is centered at lo + hi - 1 in the interleaved index
+            let expected = (0..(2 * n).saturating_sub(1))
+                .map(|c| {
+                    (0..=(c + 1) / 2)
+                        .map(|lo| (lo, c + 1 - lo))
+                        .filter(|&(lo, hi)| hi <= n && is_palindrome(&s[lo..hi]))
+                        .map(|(lo, hi)| hi - lo)
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(manacher(&s), expected);
+        }
+    }
+}
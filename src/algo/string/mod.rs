@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+
+/// Aho-Corasick automaton with [Monoid](crate::algebra::Monoid) weighted patterns:
+/// build O(Σ|P| σ) for the full transition table over σ symbols, O(1) per transition by symbol index,
+/// scan O(N logσ + matches).
+pub mod aho_corasick;
+/// Palindromic tree: O(N) nodes and amortized O(1) per push.
+pub mod eertree;
+/// Prefix function and KMP matcher: O(N + M).
+pub mod kmp;
+/// Longest palindromes at every center: O(N).
+pub mod manacher;
+/// SA-IS suffix array: O(N), Kasai LCP array: O(N),
+/// and LCP of arbitrary suffixes in O(1) with [SparseTable](crate::structure::ranged::sparse_table::SparseTable).
+pub mod suffix_array;
+/// Online suffix automaton: O(N) states and amortized O(1) per extension.
+pub mod suffix_automaton;
+/// Z-algorithm: O(N).
+pub mod z_algorithm;
+
+/// Transition table of an automaton state, for [suffix_automaton] and [eertree].
+pub trait Transition<C>: Clone + Default {
+    fn get(&self, c: &C) -> Option<usize>;
+    fn set(&mut self, c: C, to: usize);
+}
+
+/// For arbitrary alphabets. O(log σ) per transition.
+impl<C: Ord + Clone> Transition<C> for BTreeMap<C, usize> {
+    fn get(&self, c: &C) -> Option<usize> {
+        BTreeMap::get(self, c).copied()
+    }
+
+    fn set(&mut self, c: C, to: usize) {
+        self.insert(c, to);
+    }
+}
+
+/// For the fixed alphabet `0..K`. O(1) per transition, but O(K) memory per state.
+#[derive(Clone, Debug)]
+pub struct ArrayTransition<const K: usize>([usize; K]);
+
+impl<const K: usize> Default for ArrayTransition<K> {
+    fn default() -> Self {
+        Self([usize::MAX; K])
+    }
+}
+
+impl<C: Copy + Into<usize>, const K: usize> Transition<C> for ArrayTransition<K> {
+    fn get(&self, c: &C) -> Option<usize> {
+        Some(self.0[(*c).into()]).filter(|&v| v != usize::MAX)
+    }
+
+    fn set(&mut self, c: C, to: usize) {
+        self.0[c.into()] = to;
+    }
+}
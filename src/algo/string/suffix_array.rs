@@ -0,0 +1,250 @@
+use crate::algebra::typical::MinMonoid;
+use crate::structure::ranged::sparse_table::SparseTable;
+use crate::structure::ranged::RangeFold;
+
+/// Sorts the suffixes by comparing them directly, for short inputs.
+fn sa_naive<T: Ord>(s: &[T]) -> Vec<usize> {
+    let mut sa = (0..s.len()).collect::<Vec<_>>();
+    sa.sort_by(|&l, &r| s[l..].cmp(&s[r..]));
+    sa
+}
+
+/// SA-IS over `s[i] \in [0, upper]`.
+pub fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    if n < 10 {
+        return sa_naive(s);
+    }
+    // ls[i]: whether s[i..] is S-type (smaller than s[i+1..])
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] {
+            ls[i + 1]
+        } else {
+            s[i] < s[i + 1]
+        };
+    }
+    // bucket heads of L-type (sum_l) and S-type (sum_s) for each character
+    let mut sum_l = vec![0; upper + 2];
+    let mut sum_s = vec![0; upper + 2];
+    for i in 0..n {
+        if ls[i] {
+            sum_l[s[i] + 1] += 1;
+        } else {
+            sum_s[s[i]] += 1;
+        }
+    }
+    for c in 0..=upper {
+        sum_s[c] += sum_l[c];
+        sum_l[c + 1] += sum_s[c];
+    }
+
+    // values in sa are 1-origin while inducing, 0 means empty.
+    let induce = |sa: &mut [usize], lms: &[usize]| {
+        sa.iter_mut().for_each(|x| *x = 0);
+        let mut buf = sum_s.clone();
+        for &d in lms {
+            if d != n {
+                sa[buf[s[d]]] = d + 1;
+                buf[s[d]] += 1;
+            }
+        }
+        buf.copy_from_slice(&sum_l);
+        sa[buf[s[n - 1]]] = n;
+        buf[s[n - 1]] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v >= 2 && !ls[v - 2] {
+                sa[buf[s[v - 2]]] = v - 1;
+                buf[s[v - 2]] += 1;
+            }
+        }
+        buf.copy_from_slice(&sum_l);
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v >= 2 && ls[v - 2] {
+                buf[s[v - 2] + 1] -= 1;
+                sa[buf[s[v - 2] + 1]] = v - 1;
+            }
+        }
+    };
+
+    // lms_map[i]: 1-origin index of the LMS position i, 0 if i is not LMS.
+    let lms = (1..n).filter(|&i| !ls[i - 1] && ls[i]).collect::<Vec<_>>();
+    let m = lms.len();
+    let mut lms_map = vec![0; n + 1];
+    for (k, &i) in lms.iter().enumerate() {
+        lms_map[i] = k + 1;
+    }
+    let mut sa = vec![0; n];
+    induce(&mut sa, &lms);
+
+    if m > 0 {
+        let mut sorted_lms = sa
+            .iter()
+            .map(|&v| v - 1)
+            .filter(|&v| lms_map[v] != 0)
+            .collect::<Vec<_>>();
+        // name the LMS substrings, then sort them recursively
+        let mut rec_s = vec![0; m];
+        let mut rec_upper = 0;
+        for i in 1..m {
+            let (mut l, mut r) = (sorted_lms[i - 1], sorted_lms[i]);
+            let end_l = lms.get(lms_map[l]).copied().unwrap_or(n);
+            let end_r = lms.get(lms_map[r]).copied().unwrap_or(n);
+            let same = end_l - l == end_r - r && {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+                l != n && r != n && s[l] == s[r]
+            };
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i]] - 1] = rec_upper;
+        }
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        for (lms_i, &k) in sorted_lms.iter_mut().zip(&rec_sa) {
+            *lms_i = lms[k];
+        }
+        induce(&mut sa, &sorted_lms);
+    }
+    sa.iter_mut().for_each(|x| *x -= 1);
+    sa
+}
+
+/// Returns the suffix array of `s` over any ordered alphabet, by compressing it first.
+pub fn suffix_array<T: Ord>(s: &[T]) -> Vec<usize> {
+    let mut idx = (0..s.len()).collect::<Vec<_>>();
+    idx.sort_by(|&i, &j| s[i].cmp(&s[j]));
+    let mut compressed = vec![0; s.len()];
+    let mut upper = 0;
+    for k in 1..idx.len() {
+        if s[idx[k - 1]] != s[idx[k]] {
+            upper += 1;
+        }
+        compressed[idx[k]] = upper;
+    }
+    sa_is(&compressed, upper)
+}
+
+/// Returns the suffix array of a byte string, without compression.
+pub fn suffix_array_bytes(s: &[u8]) -> Vec<usize> {
+    sa_is(&s.iter().map(|&c| c as usize).collect::<Vec<_>>(), 255)
+}
+
+/// Kasai's algorithm: `lcp[k]` is the longest common prefix of `s[sa[k]..]` and `s[sa[k + 1]..]`.
+pub fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return vec![];
+    }
+    let mut rank = vec![0; n];
+    for (k, &i) in sa.iter().enumerate() {
+        rank[i] = k;
+    }
+    let mut lcp = vec![0; n - 1];
+    let mut h = 0usize;
+    for i in 0..n {
+        h = h.saturating_sub(1);
+        if rank[i] == 0 {
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[rank[i] - 1] = h;
+    }
+    lcp
+}
+
+/// Longest common prefix of any two suffixes: build O(N logN), query O(1).
+#[derive(Debug, Clone)]
+pub struct LcpQuery {
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    table: SparseTable<usize, MinMonoid>,
+}
+
+impl LcpQuery {
+    pub fn new<T: Ord>(s: &[T]) -> Self {
+        let sa = suffix_array(s);
+        let lcp = lcp_array(s, &sa);
+        let mut rank = vec![0; s.len()];
+        for (k, &i) in sa.iter().enumerate() {
+            rank[i] = k;
+        }
+        Self {
+            sa,
+            rank,
+            table: SparseTable::from(lcp),
+        }
+    }
+
+    pub fn suffix_array(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Returns the longest common prefix of `s[i..]` and `s[j..]`.
+    pub fn lcp(&mut self, i: usize, j: usize) -> usize {
+        if i == j {
+            return self.sa.len() - i;
+        }
+        let (ri, rj) = (
+            self.rank[i].min(self.rank[j]),
+            self.rank[i].max(self.rank[j]),
+        );
+        self.table.fold_in(ri..rj)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{lcp_array, suffix_array, suffix_array_bytes, LcpQuery};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn naive_lcp(a: &[u8], b: &[u8]) -> usize {
+        a.iter().zip(b).take_while(|(x, y)| x == y).count()
+    }
+
+    #[test]
+    fn sa_mississippi() {
+        let s = b"mississippi";
+        assert_eq!(
+            suffix_array_bytes(s),
+            vec![10, 7, 4, 1, 0, 9, 8, 6, 3, 5, 2]
+        );
+        assert_eq!(
+            lcp_array(s, &suffix_array(s)),
+            vec![1, 1, 4, 0, 0, 1, 0, 2, 1, 3]
+        );
+    }
+
+    #[test]
+    fn sa_random() {
+        let mut rng = Pcg64::seed_from_u64(28);
+        for _ in 0..300 {
+            let n = rng.gen_range(0..200);
+            let sigma = rng.gen_range(1..4);
+            let s = (0..n)
+                .map(|_| b'a' + rng.gen_range(0..sigma))
+                .collect::<Vec<_>>();
+            let mut expected = (0..n).collect::<Vec<_>>();
+            expected.sort_by_key(|&i| &s[i..]);
+            assert_eq!(suffix_array_bytes(&s), expected);
+            let wide = s.iter().map(|&c| -(c as i64)).collect::<Vec<_>>();
+            let mut expected_wide = (0..n).collect::<Vec<_>>();
+            expected_wide.sort_by_key(|&i| &wide[i..]);
+            assert_eq!(suffix_array(&wide), expected_wide);
+
+            let mut lq = LcpQuery::new(&s);
+            for _ in 0..20.min(n) {
+                let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                assert_eq!(lq.lcp(i, j), naive_lcp(&s[i..], &s[j..]));
+            }
+        }
+    }
+}
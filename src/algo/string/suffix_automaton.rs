@@ -0,0 +1,253 @@
+use crate::algo::string::Transition;
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone)]
+struct State<M> {
+    len: usize,
+    link: Option<usize>,
+    next: M,
+    /// whether this state was created as a prefix (not a clone)
+    is_prefix: bool,
+}
+
+/// The minimal DFA accepting the suffixes of the inserted string, built online.
+///
+/// `M` is the transition table of each state, e.g. `BTreeMap<C, usize>` or [ArrayTransition](super::ArrayTransition).
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton<C, M> {
+    symbol: PhantomData<C>,
+    states: Vec<State<M>>,
+    last: usize,
+    endpos: Option<Vec<usize>>,
+}
+
+impl<C: Clone, M: Transition<C>> Default for SuffixAutomaton<C, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clone, M: Transition<C>> From<&[C]> for SuffixAutomaton<C, M> {
+    fn from(s: &[C]) -> Self {
+        let mut sa = Self::new();
+        for c in s {
+            sa.extend(c.clone());
+        }
+        sa
+    }
+}
+
+impl<C: Clone, M: Transition<C>> SuffixAutomaton<C, M> {
+    pub const ROOT: usize = 0;
+
+    pub fn new() -> Self {
+        Self {
+            symbol: Default::default(),
+            states: vec![State {
+                len: 0,
+                link: None,
+                next: M::default(),
+                is_prefix: false,
+            }],
+            last: 0,
+            endpos: None,
+        }
+    }
+
+    /// Number of states, at most `2N - 1` for `N >= 2`.
+    pub fn num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Whether only the root exists, i.e. the string is empty.
+    pub fn is_trivial(&self) -> bool {
+        self.num_states() == 1
+    }
+
+    /// Appends `c` to the string. Amortized O(1) transitions.
+    pub fn extend(&mut self, c: C) {
+        self.endpos = None;
+        let cur = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: M::default(),
+            is_prefix: true,
+        });
+        let mut p = Some(self.last);
+        while let Some(v) = p {
+            if self.states[v].next.get(&c).is_some() {
+                break;
+            }
+            self.states[v].next.set(c.clone(), cur);
+            p = self.states[v].link;
+        }
+        self.states[cur].link = Some(match p {
+            None => Self::ROOT,
+            Some(p) => {
+                let q = self.states[p].next.get(&c).unwrap();
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        is_prefix: false,
+                        ..self.states[q].clone()
+                    });
+                    let mut p = Some(p);
+                    while let Some(v) = p {
+                        if self.states[v].next.get(&c) != Some(q) {
+                            break;
+                        }
+                        self.states[v].next.set(c.clone(), clone);
+                        p = self.states[v].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        });
+        self.last = cur;
+    }
+
+    /// Transition from `state` by `c`.
+    pub fn next(&self, state: usize, c: &C) -> Option<usize> {
+        self.states[state].next.get(c)
+    }
+
+    /// Returns the state reached by reading `s` from the root, if `s` is a substring.
+    pub fn state(&self, s: &[C]) -> Option<usize> {
+        s.iter().try_fold(Self::ROOT, |v, c| self.next(v, c))
+    }
+
+    pub fn contains(&self, s: &[C]) -> bool {
+        self.state(s).is_some()
+    }
+
+    /// Number of distinct non-empty substrings.
+    pub fn distinct_substrings(&self) -> usize {
+        self.states
+            .iter()
+            .skip(1)
+            .map(|s| s.len - self.states[s.link.unwrap()].len)
+            .sum()
+    }
+
+    /// `|endpos(v)|` for each state `v`, i.e. the number of occurrences of its substrings.
+    /// Computed lazily, and cached until the next [SuffixAutomaton::extend].
+    pub fn endpos_sizes(&mut self) -> &[usize] {
+        if self.endpos.is_none() {
+            let mut order = (0..self.num_states()).collect::<Vec<_>>();
+            order.sort_by_key(|&v| std::cmp::Reverse(self.states[v].len));
+            let mut cnt = self
+                .states
+                .iter()
+                .map(|s| usize::from(s.is_prefix))
+                .collect::<Vec<_>>();
+            for v in order {
+                if let Some(l) = self.states[v].link {
+                    cnt[l] += cnt[v];
+                }
+            }
+            self.endpos = Some(cnt);
+        }
+        self.endpos.as_ref().unwrap()
+    }
+
+    /// Number of occurrences of `pattern` in the string. The empty pattern occurs `N + 1` times.
+    pub fn occurrences(&mut self, pattern: &[C]) -> usize {
+        if pattern.is_empty() {
+            return self.states[self.last].len + 1;
+        }
+        match self.state(pattern) {
+            Some(v) => self.endpos_sizes()[v],
+            None => 0,
+        }
+    }
+
+    /// Returns `(len, end)` such that `t[end - len..end]` is a longest common substring.
+    pub fn longest_common_substring(&self, t: &[C]) -> (usize, usize) {
+        let (mut v, mut l) = (Self::ROOT, 0);
+        let mut best = (0, 0);
+        for (i, c) in t.iter().enumerate() {
+            loop {
+                if let Some(u) = self.next(v, c) {
+                    v = u;
+                    l += 1;
+                    break;
+                }
+                match self.states[v].link {
+                    Some(link) => {
+                        v = link;
+                        l = self.states[v].len;
+                    }
+                    None => {
+                        l = 0;
+                        break;
+                    }
+                }
+            }
+            if l > best.0 {
+                best = (l, i + 1);
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SuffixAutomaton;
+    use crate::algo::string::ArrayTransition;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    fn random_string(rng: &mut Pcg64, max_len: usize) -> Vec<u8> {
+        (0..rng.gen_range(0..max_len))
+            .map(|_| rng.gen_range(0..3))
+            .collect()
+    }
+
+    fn count(s: &[u8], p: &[u8]) -> usize {
+        (0..=s.len() - p.len().min(s.len()))
+            .filter(|&i| s[i..].starts_with(p))
+            .count()
+    }
+
+    #[test]
+    fn suffix_automaton_random() {
+        let mut rng = Pcg64::seed_from_u64(31);
+        for _ in 0..200 {
+            let s = random_string(&mut rng, 20);
+            let mut map = SuffixAutomaton::<u8, BTreeMap<u8, usize>>::from(s.as_slice());
+            let mut arr = SuffixAutomaton::<u8, ArrayTransition<3>>::from(s.as_slice());
+            let substrings = (0..s.len())
+                .flat_map(|i| (i + 1..=s.len()).map(move |j| (i, j)))
+                .map(|(i, j)| &s[i..j])
+                .collect::<BTreeSet<_>>();
+            assert_eq!(map.distinct_substrings(), substrings.len());
+            assert_eq!(arr.distinct_substrings(), substrings.len());
+            assert!(s.len() < 2 || map.num_states() < 2 * s.len());
+            assert_eq!(map.is_trivial(), s.is_empty());
+            for _ in 0..10 {
+                let p = random_string(&mut rng, 4);
+                let expected = if p.len() > s.len() { 0 } else { count(&s, &p) };
+                assert_eq!(map.contains(&p), expected > 0);
+                assert_eq!(map.occurrences(&p), expected);
+                assert_eq!(arr.occurrences(&p), expected);
+            }
+            let t = random_string(&mut rng, 20);
+            let (len, end) = map.longest_common_substring(&t);
+            assert!(map.contains(&t[end - len..end]));
+            let longest = (0..t.len())
+                .flat_map(|i| (i..=t.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| substrings.contains(&t[i..j]))
+                .map(|(i, j)| j - i)
+                .max()
+                .unwrap_or(0);
+            assert_eq!(len, longest);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+/// Returns `z[i]`: the longest common prefix of `s` and `s[i..]`. Note that `z[0] = s.len()`.
+pub fn z_algorithm<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // s[l..r] matches s[0..r - l]
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < r { (r - i).min(z[i - l]) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if r < i + k {
+            l = i;
+            r = i + k;
+        }
+    }
+    z
+}
+
+#[cfg(test)]
+mod test {
+    use super::z_algorithm;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn z_random() {
+        assert_eq!(z_algorithm(b"abacaba"), vec![7, 0, 1, 0, 3, 0, 1]);
+        let mut rng = Pcg64::seed_from_u64(0);
+        for _ in 0..200 {
+            let s = (0..rng.gen_range(0..30))
+                .map(|_| rng.gen_range(0..3))
+                .collect::<Vec<u32>>();
+            let expected = (0..s.len())
+                .map(|i| s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count())
+                .collect::<Vec<_>>();
+            assert_eq!(z_algorithm(&s), expected);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Runs an interactive solution against a local judge, connected by pipes.
+//!
+//! ```text
+//! cargo run --bin interactive -- --judge "./judge 42" [--limit 30] [--tl 10] [--log transcript.txt] -- ./main
+//! ```
+//!
+//! The judge accepts by exiting with 0. Every line is logged, `>` from the solution and `<` from the judge.
+
+use rust_procon::interactive::run_processes;
+use rust_procon::stress::Command;
+use std::time::Duration;
+use std::{env, fs, process};
+
+const USAGE: &str = "usage: interactive --judge <cmd> [--limit <queries>] [--tl <seconds>] [--log <file>] -- <solution> [args...]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut judge, mut solution, mut limit, mut log) = (None, None, None, None);
+    let mut timeout = Duration::from_secs(10);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            let program = args.next().unwrap_or_else(|| fail(USAGE));
+            solution = Some(Command {
+                program,
+                args: args.by_ref().collect(),
+            });
+            break;
+        }
+        let value = args.next().unwrap_or_else(|| fail(USAGE));
+        match arg.as_str() {
+            "--judge" => judge = Some(Command::parse(&value)),
+            "--limit" => limit = Some(value.parse().unwrap_or_else(|_| fail(USAGE))),
+            "--tl" => {
+                let seconds = value.parse::<f64>().unwrap_or_else(|_| fail(USAGE));
+                // rejects negative, NaN and too large values
+                timeout = Duration::try_from_secs_f64(seconds).unwrap_or_else(|_| fail(USAGE));
+            }
+            "--log" => log = Some(value),
+            _ => fail(USAGE),
+        }
+    }
+    let (Some(judge), Some(solution)) = (judge, solution) else {
+        fail(USAGE);
+    };
+    let session =
+        run_processes(&solution, &judge, limit, timeout).unwrap_or_else(|e| fail(&e.to_string()));
+    match log {
+        Some(path) => fs::write(&path, session.transcript.to_string())
+            .unwrap_or_else(|e| fail(&e.to_string())),
+        None => eprint!("{}", session.transcript),
+    }
+    println!("queries: {}", session.queries);
+    println!(
+        "solution: {:?}, judge: {:?}",
+        session.solution_status, session.judge_status
+    );
+    if session.limit_exceeded {
+        println!("query limit exceeded");
+    }
+    if session.timed_out {
+        println!("time limit exceeded");
+    }
+    if session.accepted() {
+        println!("AC");
+    } else {
+        println!("rejected");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,60 @@
+//! Runs a solution against the sample cases in a directory.
+//!
+//! ```text
+//! cargo run --bin judge -- [-d test] [--tl 2.0] [--checker tokens] -- ./main [args...]
+//! ```
+//!
+//! Cases are `<name>.in` / `<name>.out` pairs. The checker is `exact`, `tokens`, `float:<eps>`,
+//! or a command called as `checker <input> <output> <answer>`.
+
+use rust_procon::judge::{judge, summary, Checker, Verdict};
+use rust_procon::stress::Command;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, process};
+
+const USAGE: &str = "usage: judge [-d <dir>] [--tl <seconds>] [--checker exact|tokens|float:<eps>|<cmd>] -- <solution> [args...]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut dir = PathBuf::from("test");
+    let mut time_limit = Duration::from_secs(2);
+    let mut checker = Checker::parse("tokens");
+    let mut command = None;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            let program = args.next().unwrap_or_else(|| fail(USAGE));
+            command = Some(Command {
+                program,
+                args: args.by_ref().collect(),
+            });
+            break;
+        }
+        let value = args.next().unwrap_or_else(|| fail(USAGE));
+        match arg.as_str() {
+            "-d" => dir = PathBuf::from(value),
+            "--tl" => {
+                let seconds = value.parse::<f64>().unwrap_or_else(|_| fail(USAGE));
+                // rejects negative, NaN and too large values
+                time_limit = Duration::try_from_secs_f64(seconds).unwrap_or_else(|_| fail(USAGE));
+            }
+            "--checker" => checker = Checker::parse(&value),
+            _ => fail(USAGE),
+        }
+    }
+    let command = command.unwrap_or_else(|| fail(USAGE));
+    let results = judge(&command, &dir, &checker, time_limit)
+        .unwrap_or_else(|e| fail(&format!("{}: {}", dir.display(), e)));
+    if results.is_empty() {
+        fail(&format!("no cases in {}", dir.display()));
+    }
+    print!("{}", summary(&results));
+    if results.iter().any(|r| r.verdict != Verdict::AC) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,60 @@
+//! Compares a solution against a reference on generated inputs.
+//!
+//! ```text
+//! cargo run --bin stress -- --gen ./gen --ref ./naive --sol ./main [-n 1000] [--seed 0] [--cmp tokens] [--save failed]
+//! ```
+//!
+//! The generator gets the seed as its last argument. `--cmp` is `exact`, `tokens` or `float:<eps>`.
+
+use rust_procon::stress::{Command, Comparator, Stress};
+use std::{env, process};
+
+const USAGE: &str = "usage: stress --gen <cmd> --ref <cmd> --sol <cmd> [-n <iterations>] [--seed <seed>] [--cmp exact|tokens|float:<eps>] [--save <dir>]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut generator, mut reference, mut candidate) = (None, None, None);
+    let mut stress = Stress::new();
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| fail(USAGE));
+        match arg.as_str() {
+            "--gen" => generator = Some(Command::parse(&value)),
+            "--ref" => reference = Some(Command::parse(&value)),
+            "--sol" => candidate = Some(Command::parse(&value)),
+            "-n" => stress = stress.iterations(value.parse().unwrap_or_else(|_| fail(USAGE))),
+            "--seed" => stress = stress.seed(value.parse().unwrap_or_else(|_| fail(USAGE))),
+            "--cmp" => {
+                stress = stress.comparator(value.parse::<Comparator>().unwrap_or_else(|e| fail(&e)))
+            }
+            "--save" => stress = stress.save_to(value),
+            _ => fail(USAGE),
+        }
+    }
+    let (Some(mut generator), Some(mut reference), Some(mut candidate)) =
+        (generator, reference, candidate)
+    else {
+        fail(USAGE);
+    };
+    match stress.run(&mut generator, &mut reference, &mut candidate) {
+        Ok(passed) => println!("all {} cases passed", passed),
+        Err(failure) => {
+            let show = |r: &Result<String, String>| match r {
+                Ok(s) => s.clone(),
+                Err(e) => format!("runtime error: {}", e),
+            };
+            println!("failed at seed {}", failure.seed);
+            println!("--- input\n{}", failure.input);
+            println!("--- expected\n{}", show(&failure.expected));
+            println!("--- actual\n{}", show(&failure.actual));
+            if let Some(e) = &failure.save_error {
+                eprintln!("failed to save the case: {}", e);
+            }
+            process::exit(1);
+        }
+    }
+}
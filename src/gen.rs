@@ -0,0 +1,371 @@
+use rand::distributions::uniform::SampleUniform;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// A uniformly random permutation of `0..n`.
+pub fn permutation<R: Rng>(rng: &mut R, n: usize) -> Vec<usize> {
+    let mut p = (0..n).collect::<Vec<_>>();
+    p.shuffle(rng);
+    p
+}
+
+/// `n` values drawn independently from `range`.
+pub fn array<R: Rng, T: SampleUniform + PartialOrd + Clone>(
+    rng: &mut R,
+    n: usize,
+    range: Range<T>,
+) -> Vec<T> {
+    (0..n).map(|_| rng.gen_range(range.clone())).collect()
+}
+
+/// `n` distinct values from `range` in random order, by Floyd's sampling: O(N) expected.
+pub fn distinct<R: Rng>(rng: &mut R, n: usize, range: Range<i64>) -> Vec<i64> {
+    let len = range.end.saturating_sub(range.start).max(0) as u64;
+    assert!(n as u64 <= len, "range has less than {} values", n);
+    let mut set = HashSet::with_capacity(n);
+    let mut res = Vec::with_capacity(n);
+    for j in len - n as u64..len {
+        let t = rng.gen_range(0..=j);
+        let x = if set.insert(t) { t } else { j };
+        set.insert(x);
+        res.push(range.start + x as i64);
+    }
+    res.shuffle(rng);
+    res
+}
+
+/// `n` characters drawn independently from `alphabet`.
+pub fn string<R: Rng>(rng: &mut R, n: usize, alphabet: &[u8]) -> String {
+    (0..n)
+        .map(|_| *alphabet.choose(rng).expect("empty alphabet") as char)
+        .collect()
+}
+
+/// Shuffles the labels of vertices, the order of edges and the direction of each edge.
+fn relabel<R: Rng>(rng: &mut R, n: usize, mut edges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    let p = permutation(rng, n);
+    for (u, v) in edges.iter_mut() {
+        (*u, *v) = (p[*u], p[*v]);
+        if rng.gen() {
+            std::mem::swap(u, v);
+        }
+    }
+    edges.shuffle(rng);
+    edges
+}
+
+/// Relabeled tree where the vertex `i` is attached to `parent(rng, i)`, which must be less than `i`.
+fn tree_by_parent<R: Rng>(
+    rng: &mut R,
+    n: usize,
+    mut parent: impl FnMut(&mut R, usize) -> usize,
+) -> Vec<(usize, usize)> {
+    let edges = (1..n).map(|i| (parent(rng, i), i)).collect();
+    relabel(rng, n, edges)
+}
+
+/// The `n-1` edges of a uniformly random labeled tree, decoded from a random Prüfer sequence: O(N).
+pub fn tree<R: Rng>(rng: &mut R, n: usize) -> Vec<(usize, usize)> {
+    if n <= 2 {
+        return (1..n).map(|v| (0, v)).collect();
+    }
+    let code = (0..n - 2).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+    let mut degree = vec![1; n];
+    for &v in &code {
+        degree[v] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    // the smallest leaf is `leaf`, or the vertex which just became a leaf if that is smaller
+    let mut ptr = degree.iter().position(|&d| d == 1).unwrap();
+    let mut leaf = ptr;
+    for &v in &code {
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < ptr {
+            leaf = v;
+        } else {
+            ptr += 1 + degree[ptr + 1..].iter().position(|&d| d == 1).unwrap();
+            leaf = ptr;
+        }
+    }
+    edges.push((leaf, n - 1));
+    edges
+}
+
+/// A deep tree: each vertex is attached to one of the previous two, so the diameter is about `2N/3`.
+pub fn path_like_tree<R: Rng>(rng: &mut R, n: usize) -> Vec<(usize, usize)> {
+    tree_by_parent(rng, n, |rng, i| rng.gen_range(i.saturating_sub(2)..i))
+}
+
+/// A shallow tree: each vertex is attached to one of at most three hubs.
+pub fn star_like_tree<R: Rng>(rng: &mut R, n: usize) -> Vec<(usize, usize)> {
+    tree_by_parent(rng, n, |rng, i| rng.gen_range(0..i.min(3)))
+}
+
+/// A path of random length, with every other vertex attached to a vertex on it.
+pub fn caterpillar<R: Rng>(rng: &mut R, n: usize) -> Vec<(usize, usize)> {
+    if n == 0 {
+        return vec![];
+    }
+    let spine = rng.gen_range(1..=n);
+    tree_by_parent(rng, n, |rng, i| {
+        if i < spine {
+            i - 1
+        } else {
+            rng.gen_range(0..spine)
+        }
+    })
+}
+
+/// `m` distinct edges `(u, v)` with `u != v` of an undirected graph: O(N + M) expected.
+pub fn simple_graph<R: Rng>(rng: &mut R, n: usize, m: usize) -> Vec<(usize, usize)> {
+    extend_simple(rng, n, m, vec![])
+}
+
+/// Adds random edges to `edges` until there are `m`, keeping the graph simple.
+fn extend_simple<R: Rng>(
+    rng: &mut R,
+    n: usize,
+    m: usize,
+    mut edges: Vec<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    let max = n * n.saturating_sub(1) / 2;
+    assert!(
+        m <= max,
+        "a simple graph on {} vertices has at most {} edges",
+        n,
+        max
+    );
+    let key = |(u, v): (usize, usize)| (u.min(v), u.max(v));
+    let mut used = edges.iter().map(|&e| key(e)).collect::<HashSet<_>>();
+    if 2 * m <= max {
+        while edges.len() < m {
+            let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            if u != v && used.insert(key((u, v))) {
+                edges.push((u, v));
+            }
+        }
+    } else {
+        let mut rest = (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .filter(|e| !used.contains(e))
+            .collect::<Vec<_>>();
+        rest.shuffle(rng);
+        let k = m - edges.len();
+        edges.extend(rest.into_iter().take(k));
+    }
+    edges.shuffle(rng);
+    edges
+}
+
+/// A connected simple graph with `m >= n - 1` edges: a uniform tree plus random edges.
+pub fn connected_graph<R: Rng>(rng: &mut R, n: usize, m: usize) -> Vec<(usize, usize)> {
+    assert!(
+        m + 1 >= n,
+        "a connected graph on {} vertices has at least {} edges",
+        n,
+        n - 1
+    );
+    let t = tree(rng, n);
+    let edges = extend_simple(rng, n, m, t);
+    relabel(rng, n, edges)
+}
+
+/// A simple directed acyclic graph with `m` edges, whose vertices are in a random topological order.
+pub fn dag<R: Rng>(rng: &mut R, n: usize, m: usize) -> Vec<(usize, usize)> {
+    let order = permutation(rng, n);
+    simple_graph(rng, n, m)
+        .into_iter()
+        .map(|(u, v)| {
+            let (u, v) = (u.min(v), u.max(v));
+            (order[u], order[v])
+        })
+        .collect()
+}
+
+/// Attaches a weight drawn from `range` to each edge.
+pub fn with_weights<R: Rng, T: SampleUniform + PartialOrd + Clone>(
+    rng: &mut R,
+    edges: &[(usize, usize)],
+    range: Range<T>,
+) -> Vec<(usize, usize, T)> {
+    edges
+        .iter()
+        .map(|&(u, v)| (u, v, rng.gen_range(range.clone())))
+        .collect()
+}
+
+/// A range `l..r` with `0 <= l <= r <= n`, possibly empty.
+pub fn range<R: Rng>(rng: &mut R, n: usize) -> Range<usize> {
+    let (a, b) = (rng.gen_range(0..=n), rng.gen_range(0..=n));
+    a.min(b)..a.max(b)
+}
+
+/// A query against the `structure::ranged` types.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Query<E> {
+    /// `PointAssign::set_at(elem, index)`.
+    Set(usize, E),
+    /// `RangeFold::fold_in(range)`.
+    Fold(Range<usize>),
+}
+
+/// `q` queries over an array of length `n >= 1`, half of them [Query::Set] with elements from `elem`.
+pub fn queries<R: Rng, E>(
+    rng: &mut R,
+    n: usize,
+    q: usize,
+    mut elem: impl FnMut(&mut R) -> E,
+) -> Vec<Query<E>> {
+    assert!(n > 0);
+    (0..q)
+        .map(|_| {
+            if rng.gen() {
+                let i = rng.gen_range(0..n);
+                Query::Set(i, elem(rng))
+            } else {
+                Query::Fold(range(rng, n))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebra::typical::AdditiveStruct;
+    use crate::structure::ranged::naive_vec::NaiveVec;
+    use crate::structure::ranged::segment_tree::SegmentTree;
+    use crate::structure::ranged::{PointAssign, RangeFold};
+    use ac_library::Dsu;
+    use rand::SeedableRng;
+    use rand_pcg::Pcg64;
+
+    fn is_tree(n: usize, edges: &[(usize, usize)]) -> bool {
+        let mut dsu = Dsu::new(n);
+        edges.len() + 1 == n.max(1)
+            && edges
+                .iter()
+                .all(|&(u, v)| u < n && v < n && !dsu.same(u, v) && dsu.merge(u, v) < n)
+    }
+
+    fn is_simple(n: usize, edges: &[(usize, usize)]) -> bool {
+        let set = edges
+            .iter()
+            .map(|&(u, v)| (u.min(v), u.max(v)))
+            .collect::<HashSet<_>>();
+        set.len() == edges.len() && edges.iter().all(|&(u, v)| u != v && u < n && v < n)
+    }
+
+    #[test]
+    fn gen_trees() {
+        let mut rng = Pcg64::seed_from_u64(46);
+        for n in (0..30).chain([1000]) {
+            assert!(is_tree(n, &tree(&mut rng, n)));
+            assert!(is_tree(n, &path_like_tree(&mut rng, n)));
+            assert!(is_tree(n, &star_like_tree(&mut rng, n)));
+            assert!(is_tree(n, &caterpillar(&mut rng, n)));
+        }
+        // all 16 labeled trees on 4 vertices appear
+        let mut seen = HashSet::new();
+        for _ in 0..1000 {
+            let mut t = tree(&mut rng, 4)
+                .into_iter()
+                .map(|(u, v)| (u.min(v), u.max(v)))
+                .collect::<Vec<_>>();
+            t.sort();
+            seen.insert(t);
+        }
+        assert_eq!(seen.len(), 16);
+    }
+
+    #[test]
+    fn gen_graphs() {
+        let mut rng = Pcg64::seed_from_u64(46);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..12);
+            let max = n * (n - 1) / 2;
+            let m = rng.gen_range(0..=max);
+            let g = simple_graph(&mut rng, n, m);
+            assert!(g.len() == m && is_simple(n, &g));
+
+            let m = rng.gen_range(n - 1..=max);
+            let g = connected_graph(&mut rng, n, m);
+            assert!(g.len() == m && is_simple(n, &g));
+            let mut dsu = Dsu::new(n);
+            g.iter().for_each(|&(u, v)| {
+                dsu.merge(u, v);
+            });
+            assert_eq!(dsu.size(0), n);
+
+            let m = rng.gen_range(0..=max);
+            let g = dag(&mut rng, n, m);
+            assert!(is_simple(n, &g));
+            let mut indeg = vec![0; n];
+            g.iter().for_each(|&(_, v)| indeg[v] += 1);
+            let mut stack = (0..n).filter(|&v| indeg[v] == 0).collect::<Vec<_>>();
+            let mut visited = 0;
+            while let Some(u) = stack.pop() {
+                visited += 1;
+                for &(_, v) in g.iter().filter(|e| e.0 == u) {
+                    indeg[v] -= 1;
+                    if indeg[v] == 0 {
+                        stack.push(v);
+                    }
+                }
+            }
+            assert_eq!(visited, n);
+
+            let w = with_weights(&mut rng, &g, -5i64..5);
+            assert!(w.iter().all(|&(_, _, c)| (-5..5).contains(&c)));
+        }
+    }
+
+    #[test]
+    fn gen_sequences() {
+        let mut rng = Pcg64::seed_from_u64(46);
+        let mut p = permutation(&mut rng, 100);
+        p.sort();
+        assert_eq!(p, (0..100).collect::<Vec<_>>());
+        for len in 0..20 {
+            let mut d = distinct(&mut rng, len, -10..10);
+            assert!(d.iter().all(|x| (-10..10).contains(x)));
+            d.sort();
+            d.dedup();
+            assert_eq!(d.len(), len);
+        }
+        let s = string(&mut rng, 50, b"ab");
+        assert!(s.len() == 50 && s.bytes().all(|c| c == b'a' || c == b'b'));
+        assert!(array(&mut rng, 50, 0.0..1.0)
+            .iter()
+            .all(|x| (0.0..1.0).contains(x)));
+
+        // the same seed gives the same data
+        let mut a = Pcg64::seed_from_u64(7);
+        let mut b = Pcg64::seed_from_u64(7);
+        assert_eq!(
+            connected_graph(&mut a, 50, 100),
+            connected_graph(&mut b, 50, 100)
+        );
+    }
+
+    #[test]
+    fn gen_queries() {
+        let mut rng = Pcg64::seed_from_u64(46);
+        let n = 50;
+        let a = array(&mut rng, n, -100i64..100);
+        let mut seg = SegmentTree::<i64, AdditiveStruct>::from(&a[..]);
+        let mut naive = NaiveVec::<i64, AdditiveStruct>::from(a);
+        for query in queries(&mut rng, n, 1000, |rng| rng.gen_range(-100..100)) {
+            match query {
+                Query::Set(i, x) => {
+                    seg.set_at(x, i);
+                    naive.set_at(x, i);
+                }
+                Query::Fold(r) => assert_eq!(seg.fold_in(r.clone()), naive.fold_in(r)),
+            }
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use crate::stress::Command;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{self, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The solution side of an interactive problem: sends lines and reads responses.
+///
+/// Write solutions generic over this, so that they run on [StdioChannel] in submission
+/// and on [MockChannel] in tests.
+pub trait Channel {
+    fn send(&mut self, line: &str);
+    /// The next line from the judge, without the newline.
+    fn recv(&mut self) -> String;
+
+    /// Sends a query and returns the response.
+    fn query(&mut self, line: &str) -> String {
+        self.send(line);
+        self.recv()
+    }
+}
+
+/// Over stdin and stdout, flushing after each line.
+pub struct StdioChannel {
+    stdin: io::StdinLock<'static>,
+    stdout: io::StdoutLock<'static>,
+}
+
+impl Default for StdioChannel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdioChannel {
+    pub fn new() -> Self {
+        Self {
+            stdin: io::stdin().lock(),
+            stdout: io::stdout().lock(),
+        }
+    }
+}
+
+impl Channel for StdioChannel {
+    fn send(&mut self, line: &str) {
+        writeln!(self.stdout, "{}", line).unwrap();
+        self.stdout.flush().unwrap();
+    }
+
+    fn recv(&mut self) -> String {
+        let mut line = String::new();
+        self.stdin.read_line(&mut line).unwrap();
+        line.trim_end_matches(['\r', '\n']).to_string()
+    }
+}
+
+/// The jury side, written in Rust for [MockChannel].
+pub trait Jury {
+    /// Lines sent before the first query, such as the size of the hidden object.
+    fn start(&mut self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Responds to a line from the solution. Some lines, such as the final answer, have no response.
+    fn respond(&mut self, line: &str) -> Option<String>;
+
+    /// Judges after the solution finished.
+    fn verdict(&self) -> Result<(), String>;
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Direction {
+    ToJudge,
+    ToSolution,
+}
+
+/// The lines exchanged, in order.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Transcript(pub Vec<(Direction, String)>);
+
+/// `> ` for the solution's lines and `< ` for the judge's.
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (dir, line) in &self.0 {
+            let mark = match dir {
+                Direction::ToJudge => '>',
+                Direction::ToSolution => '<',
+            };
+            writeln!(f, "{} {}", mark, line)?;
+        }
+        Ok(())
+    }
+}
+
+/// In-process channel to a [Jury], counting the lines sent by the solution.
+pub struct MockChannel<J> {
+    jury: J,
+    pending: VecDeque<String>,
+    transcript: Transcript,
+    queries: usize,
+    limit: Option<usize>,
+}
+
+impl<J: Jury> MockChannel<J> {
+    pub fn new(mut jury: J) -> Self {
+        let pending = jury.start().into_iter().collect::<VecDeque<_>>();
+        let transcript = Transcript(
+            pending
+                .iter()
+                .map(|s| (Direction::ToSolution, s.clone()))
+                .collect(),
+        );
+        Self {
+            jury,
+            pending,
+            transcript,
+            queries: 0,
+            limit: None,
+        }
+    }
+
+    /// Lines beyond `limit` are not delivered to the jury, and make [MockChannel::finish] fail.
+    pub fn query_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    pub fn transcript(&self) -> &Transcript {
+        &self.transcript
+    }
+
+    /// The verdict of the jury, unless the query limit is exceeded.
+    pub fn finish(self) -> Result<Transcript, String> {
+        if let Some(limit) = self.limit.filter(|&l| self.queries > l) {
+            return Err(format!(
+                "query limit exceeded: {} > {}",
+                self.queries, limit
+            ));
+        }
+        self.jury.verdict().map(|_| self.transcript)
+    }
+}
+
+impl<J: Jury> Channel for MockChannel<J> {
+    fn send(&mut self, line: &str) {
+        self.queries += 1;
+        self.transcript
+            .0
+            .push((Direction::ToJudge, line.to_string()));
+        if self.limit.is_some_and(|l| self.queries > l) {
+            return;
+        }
+        if let Some(res) = self.jury.respond(line) {
+            self.transcript.0.push((Direction::ToSolution, res.clone()));
+            self.pending.push_back(res);
+        }
+    }
+
+    fn recv(&mut self) -> String {
+        self.pending.pop_front().unwrap_or_else(|| {
+            panic!(
+                "waiting for a response, but the jury sent nothing\n{}",
+                self.transcript
+            )
+        })
+    }
+}
+
+/// The result of [run_processes].
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub transcript: Transcript,
+    /// Lines sent by the solution.
+    pub queries: usize,
+    pub limit_exceeded: bool,
+    pub timed_out: bool,
+    pub solution_status: Option<process::ExitStatus>,
+    /// The judge accepts iff it exits with 0, as testlib interactors do.
+    pub judge_status: Option<process::ExitStatus>,
+}
+
+impl Session {
+    pub fn accepted(&self) -> bool {
+        !self.limit_exceeded
+            && !self.timed_out
+            && self.solution_status.is_some_and(|s| s.success())
+            && self.judge_status.is_some_and(|s| s.success())
+    }
+}
+
+/// Runs `solution` and `judge` with each stdout piped into the other's stdin, logging every line.
+/// Both are killed when the solution sends more than `limit` lines or `timeout` passes.
+pub fn run_processes(
+    solution: &Command,
+    judge: &Command,
+    limit: Option<usize>,
+    timeout: Duration,
+) -> io::Result<Session> {
+    let spawn = |c: &Command| {
+        process::Command::new(&c.program)
+            .args(&c.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+    };
+    let mut sol = spawn(solution)?;
+    let mut jdg = match spawn(judge) {
+        Ok(child) => child,
+        Err(e) => {
+            sol.kill()?;
+            sol.wait()?;
+            return Err(e);
+        }
+    };
+    let transcript = Arc::new(Mutex::new(Transcript::default()));
+    let queries = Arc::new(AtomicUsize::new(0));
+    let exceeded = Arc::new(AtomicBool::new(false));
+
+    let relay = |from: process::ChildStdout, mut to: process::ChildStdin, dir: Direction| {
+        let transcript = Arc::clone(&transcript);
+        let queries = Arc::clone(&queries);
+        let exceeded = Arc::clone(&exceeded);
+        thread::spawn(move || {
+            for line in BufReader::new(from).lines() {
+                let Ok(line) = line else { break };
+                transcript.lock().unwrap().0.push((dir, line.clone()));
+                if dir == Direction::ToJudge
+                    && limit.is_some_and(|l| queries.fetch_add(1, Ordering::SeqCst) >= l)
+                {
+                    exceeded.store(true, Ordering::SeqCst);
+                    break;
+                }
+                if writeln!(to, "{}", line).and_then(|_| to.flush()).is_err() {
+                    break;
+                }
+            }
+            // dropping `to` closes the pipe, so that the other side sees EOF
+        })
+    };
+    let to_judge = relay(
+        sol.stdout.take().unwrap(),
+        jdg.stdin.take().unwrap(),
+        Direction::ToJudge,
+    );
+    let to_solution = relay(
+        jdg.stdout.take().unwrap(),
+        sol.stdin.take().unwrap(),
+        Direction::ToSolution,
+    );
+
+    let start = Instant::now();
+    let (mut solution_status, mut judge_status) = (None, None);
+    let mut timed_out = false;
+    while solution_status.is_none() || judge_status.is_none() {
+        if solution_status.is_none() {
+            solution_status = sol.try_wait()?;
+        }
+        if judge_status.is_none() {
+            judge_status = jdg.try_wait()?;
+        }
+        let stop = exceeded.load(Ordering::SeqCst) || start.elapsed() > timeout;
+        if stop && (solution_status.is_none() || judge_status.is_none()) {
+            timed_out = !exceeded.load(Ordering::SeqCst);
+            for (child, status) in [
+                (&mut sol, &mut solution_status),
+                (&mut jdg, &mut judge_status),
+            ] {
+                if status.is_none() {
+                    child.kill()?;
+                    child.wait()?;
+                }
+            }
+            break;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    to_judge.join().unwrap();
+    to_solution.join().unwrap();
+    let transcript = transcript.lock().unwrap().clone();
+    Ok(Session {
+        transcript,
+        queries: queries.load(Ordering::SeqCst),
+        limit_exceeded: exceeded.load(Ordering::SeqCst),
+        timed_out,
+        solution_status,
+        judge_status,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Guess `secret` in `[1, n]`; `? x` is answered by `<`, `>` or `=`, and `! x` is the answer.
+    struct GuessJury {
+        n: u32,
+        secret: u32,
+        answer: Option<u32>,
+    }
+
+    impl Jury for GuessJury {
+        fn start(&mut self) -> Vec<String> {
+            vec![self.n.to_string()]
+        }
+
+        fn respond(&mut self, line: &str) -> Option<String> {
+            let (kind, x) = line.split_once(' ').unwrap();
+            let x = x.parse::<u32>().unwrap();
+            match kind {
+                "?" => Some(match x.cmp(&self.secret) {
+                    std::cmp::Ordering::Less => "<".to_string(),
+                    std::cmp::Ordering::Greater => ">".to_string(),
+                    std::cmp::Ordering::Equal => "=".to_string(),
+                }),
+                _ => {
+                    self.answer = Some(x);
+                    None
+                }
+            }
+        }
+
+        fn verdict(&self) -> Result<(), String> {
+            match self.answer {
+                Some(x) if x == self.secret => Ok(()),
+                x => Err(format!("expected {}, found {:?}", self.secret, x)),
+            }
+        }
+    }
+
+    /// Binary search, or linear search if `linear`.
+    fn solve<C: Channel>(ch: &mut C, linear: bool) {
+        let n = ch.recv().parse::<u32>().unwrap();
+        let (mut lo, mut hi) = (1, n);
+        while lo < hi {
+            let mid = if linear { lo } else { (lo + hi) / 2 };
+            match ch.query(&format!("? {}", mid)).as_str() {
+                "<" => lo = mid + 1,
+                "=" => (lo, hi) = (mid, mid),
+                _ => hi = mid - 1,
+            }
+        }
+        ch.send(&format!("! {}", lo));
+    }
+
+    #[test]
+    fn interactive_mock() {
+        for secret in 1..=100 {
+            let jury = GuessJury {
+                n: 100,
+                secret,
+                answer: None,
+            };
+            let mut ch = MockChannel::new(jury).query_limit(8);
+            solve(&mut ch, false);
+            assert!(ch.queries() <= 8);
+            assert!(ch.finish().is_ok());
+        }
+        let jury = GuessJury {
+            n: 100,
+            secret: 50,
+            answer: None,
+        };
+        let mut ch = MockChannel::new(jury).query_limit(100);
+        solve(&mut ch, false);
+        assert_eq!(ch.transcript().to_string(), "< 100\n> ? 50\n< =\n> ! 50\n");
+
+        let jury = GuessJury {
+            n: 100,
+            secret: 5,
+            answer: None,
+        };
+        let mut ch = MockChannel::new(jury).query_limit(3);
+        let panicked =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| solve(&mut ch, true)));
+        assert!(panicked.is_err());
+        assert!(ch.finish().unwrap_err().starts_with("query limit exceeded"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn interactive_processes() {
+        let sh = |script: &str| Command::new("sh").arg("-c").arg(script);
+        // the judge sends 21 and accepts 42
+        let judge = sh("echo 21; read x; test \"$x\" = 42");
+        let session = run_processes(
+            &sh("read n; echo $((n * 2))"),
+            &judge,
+            Some(5),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert!(session.accepted());
+        assert_eq!(session.transcript.to_string(), "< 21\n> 42\n");
+        assert_eq!(session.queries, 1);
+
+        let wrong = run_processes(
+            &sh("read n; echo $n"),
+            &judge,
+            None,
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert!(!wrong.accepted());
+
+        // an endless solution against an echoing judge
+        let spam = run_processes(
+            &sh("while true; do echo ping; read x; done"),
+            &sh("while read x; do echo $x; done"),
+            Some(3),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert!(spam.limit_exceeded);
+        assert!(!spam.accepted());
+        assert_eq!(spam.queries, 4);
+    }
+}
@@ -0,0 +1,384 @@
+use std::fmt::Display;
+use std::io::{self, BufWriter, Read, Write};
+
+/// Whitespace separated tokens over a byte buffer, without copying them.
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Reads the whole stdin. The buffer is leaked, so that tokens live as long as the program.
+    pub fn stdin() -> Scanner<'static> {
+        let mut buf = vec![];
+        io::stdin().read_to_end(&mut buf).unwrap();
+        Scanner::new(Box::leak(buf.into_boxed_slice()))
+    }
+
+    /// Whether only whitespace remains.
+    pub fn is_empty(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.buf.len()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    /// The next token, as a slice of the buffer.
+    pub fn token(&mut self) -> &'a [u8] {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        assert!(start < self.pos, "unexpected end of input");
+        &self.buf[start..self.pos]
+    }
+
+    pub fn str(&mut self) -> &'a str {
+        std::str::from_utf8(self.token()).expect("invalid UTF-8 token")
+    }
+
+    pub fn read<T: Readable>(&mut self) -> T::Output {
+        T::read(self)
+    }
+
+    pub fn vec<T: Readable>(&mut self, n: usize) -> Vec<T::Output> {
+        (0..n).map(|_| T::read(self)).collect()
+    }
+}
+
+/// Types read from a [Scanner]. `Output` differs from `Self` for markers such as [Usize1].
+pub trait Readable {
+    type Output;
+    fn read(sc: &mut Scanner) -> Self::Output;
+}
+
+macro_rules! impl_readable_unsigned {
+    ($($t:ty),*) => {$(
+        /// Digits only, without sign parsing.
+        impl Readable for $t {
+            type Output = $t;
+            fn read(sc: &mut Scanner) -> $t {
+                sc.token().iter().fold(0, |acc, &c| {
+                    debug_assert!(c.is_ascii_digit());
+                    acc * 10 + (c - b'0') as $t
+                })
+            }
+        }
+    )*};
+}
+
+impl_readable_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_readable_signed {
+    ($($t:ty),*) => {$(
+        impl Readable for $t {
+            type Output = $t;
+            fn read(sc: &mut Scanner) -> $t {
+                let token = sc.token();
+                let (neg, digits) = match token.split_first() {
+                    Some((b'-', rest)) => (true, rest),
+                    _ => (false, token),
+                };
+                // accumulated on the negative side, so that the minimum value does not overflow
+                let res = digits.iter().fold(0, |acc: $t, &c| {
+                    debug_assert!(c.is_ascii_digit());
+                    acc * 10 - (c - b'0') as $t
+                });
+                if neg {
+                    res
+                } else {
+                    -res
+                }
+            }
+        }
+    )*};
+}
+
+impl_readable_signed!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_readable_from_str {
+    ($($t:ty),*) => {$(
+        impl Readable for $t {
+            type Output = $t;
+            fn read(sc: &mut Scanner) -> $t {
+                sc.str().parse().unwrap()
+            }
+        }
+    )*};
+}
+
+impl_readable_from_str!(f32, f64, String);
+
+impl Readable for char {
+    type Output = char;
+    fn read(sc: &mut Scanner) -> char {
+        let token = sc.str();
+        let mut chars = token.chars();
+        let c = chars.next().unwrap();
+        assert!(chars.next().is_none(), "not a single char: {}", token);
+        c
+    }
+}
+
+/// A token as `Vec<u8>`.
+pub enum Bytes {}
+
+impl Readable for Bytes {
+    type Output = Vec<u8>;
+    fn read(sc: &mut Scanner) -> Vec<u8> {
+        sc.token().to_vec()
+    }
+}
+
+/// A token as `Vec<char>`.
+pub enum Chars {}
+
+impl Readable for Chars {
+    type Output = Vec<char>;
+    fn read(sc: &mut Scanner) -> Vec<char> {
+        sc.str().chars().collect()
+    }
+}
+
+/// A 1-indexed `usize`, read as 0-indexed.
+pub enum Usize1 {}
+
+impl Readable for Usize1 {
+    type Output = usize;
+    fn read(sc: &mut Scanner) -> usize {
+        usize::read(sc) - 1
+    }
+}
+
+/// A 1-indexed `u32`, read as 0-indexed.
+pub enum U32_1 {}
+
+impl Readable for U32_1 {
+    type Output = u32;
+    fn read(sc: &mut Scanner) -> u32 {
+        u32::read(sc) - 1
+    }
+}
+
+impl<T: Readable, const N: usize> Readable for [T; N] {
+    type Output = [T::Output; N];
+    fn read(sc: &mut Scanner) -> Self::Output {
+        std::array::from_fn(|_| T::read(sc))
+    }
+}
+
+macro_rules! impl_readable_tuple {
+    ($($t:ident),*) => {
+        impl<$($t: Readable),*> Readable for ($($t,)*) {
+            type Output = ($($t::Output,)*);
+            fn read(sc: &mut Scanner) -> Self::Output {
+                ($($t::read(sc),)*)
+            }
+        }
+    };
+}
+
+impl_readable_tuple!(A);
+impl_readable_tuple!(A, B);
+impl_readable_tuple!(A, B, C);
+impl_readable_tuple!(A, B, C, D);
+impl_readable_tuple!(A, B, C, D, E);
+
+/// Declares variables read from a [Scanner].
+///
+/// ```
+/// use rust_procon::input;
+/// use rust_procon::io::{Bytes, Scanner, Usize1};
+///
+/// let mut sc = Scanner::new(b"3 2\n1 2 3\nabc\n1 3\n2 3\n");
+/// input!(sc => n: usize, m: usize, mut a: [i64; n], s: Bytes, e: [(Usize1, Usize1); m]);
+/// a.push(4);
+/// assert_eq!(a, vec![1, 2, 3, 4]);
+/// assert_eq!(s, b"abc");
+/// assert_eq!(e, vec![(0, 2), (1, 2)]);
+/// ```
+///
+/// `[T; n]` is read as `Vec`, nested and tuples are allowed. Use [Scanner::read] for fixed-size arrays.
+#[macro_export]
+macro_rules! input {
+    ($sc:expr => $(,)?) => {};
+    ($sc:expr => mut $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let mut $var = $crate::read_value!($sc, $t);
+        $crate::input!($sc => $($($rest)*)?);
+    };
+    ($sc:expr => $var:ident : $t:tt $(, $($rest:tt)*)?) => {
+        let $var = $crate::read_value!($sc, $t);
+        $crate::input!($sc => $($($rest)*)?);
+    };
+}
+
+/// A value of the type notation of [input!].
+#[macro_export]
+macro_rules! read_value {
+    ($sc:expr, [$t:tt; $n:expr]) => {
+        (0..$n).map(|_| $crate::read_value!($sc, $t)).collect::<Vec<_>>()
+    };
+    ($sc:expr, ($($t:tt),*)) => {
+        ($($crate::read_value!($sc, $t),)*)
+    };
+    ($sc:expr, $t:ty) => {
+        $sc.read::<$t>()
+    };
+}
+
+/// Buffered output, flushed on drop.
+pub struct Writer<W: Write> {
+    out: BufWriter<W>,
+}
+
+impl Writer<io::Stdout> {
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: BufWriter::new(out),
+        }
+    }
+
+    pub fn write<T: Display>(&mut self, x: T) {
+        write!(self.out, "{}", x).unwrap();
+    }
+
+    pub fn writeln<T: Display>(&mut self, x: T) {
+        writeln!(self.out, "{}", x).unwrap();
+    }
+
+    /// Writes the items separated by `sep`, and a newline.
+    pub fn join<I>(&mut self, items: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, x) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.out.write_all(sep.as_bytes()).unwrap();
+            }
+            write!(self.out, "{}", x).unwrap();
+        }
+        self.out.write_all(b"\n").unwrap();
+    }
+
+    /// Writes the items in one line, separated by spaces.
+    pub fn words<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        self.join(items, " ");
+    }
+
+    /// Writes each item in its own line.
+    pub fn lines<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for x in items {
+            writeln!(self.out, "{}", x).unwrap();
+        }
+    }
+
+    pub fn bytes(&mut self, s: &[u8]) {
+        self.out.write_all(s).unwrap();
+        self.out.write_all(b"\n").unwrap();
+    }
+
+    /// `Yes` or `No`.
+    pub fn yes_no(&mut self, b: bool) {
+        self.writeln(if b { "Yes" } else { "No" });
+    }
+
+    pub fn flush(&mut self) {
+        self.out.flush().unwrap();
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out.into_inner().ok().unwrap()
+    }
+}
+
+/// For `write!` and `writeln!`.
+impl<W: Write> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.out.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scanner_values() {
+        let input =
+            b"  42 -7 -9223372036854775808 18446744073709551615\n3.5 x hello\n\n5 2] 3 ab 1 2 3\n";
+        let mut sc = Scanner::new(input);
+        assert_eq!(sc.read::<u32>(), 42);
+        assert_eq!(sc.read::<i32>(), -7);
+        assert_eq!(sc.read::<i64>(), i64::MIN);
+        assert_eq!(sc.read::<u64>(), u64::MAX);
+        assert_eq!(sc.read::<f64>(), 3.5);
+        assert_eq!(sc.read::<char>(), 'x');
+        assert_eq!(sc.token(), b"hello");
+        assert_eq!(sc.read::<Usize1>(), 4);
+        assert_eq!(sc.str(), "2]");
+        assert_eq!(sc.read::<(U32_1, Chars)>(), (2, vec!['a', 'b']));
+        assert!(!sc.is_empty());
+        assert_eq!(sc.read::<[i8; 3]>(), [1, 2, 3]);
+        assert!(sc.is_empty());
+    }
+
+    #[test]
+    fn scanner_input_macro() {
+        let mut sc = Scanner::new(b"2 3\n1 2 3\n4 5 6\nabc\n1 2 x\n");
+        input!(sc => h: usize, w: usize, a: [[u64; w]; h], mut s: Bytes, t: (Usize1, i32, char),);
+        s.reverse();
+        assert_eq!(a, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(s, b"cba");
+        assert_eq!(t, (0, 2, 'x'));
+    }
+
+    #[test]
+    fn writer_in_memory() {
+        let mut buf = vec![];
+        {
+            let mut out = Writer::new(&mut buf);
+            out.writeln(1);
+            out.words([1, 2, 3]);
+            out.join(&["a", "b"], ",");
+            out.lines(vec![4, 5]);
+            out.bytes(b"xyz");
+            out.yes_no(false);
+            writeln!(out, "{:.2}", 0.5).unwrap();
+            out.words(Vec::<u8>::new());
+        }
+        assert_eq!(buf, b"1\n1 2 3\na,b\n4\n5\nxyz\nNo\n0.50\n\n");
+        let mut out = Writer::new(vec![]);
+        out.write("ok");
+        assert_eq!(out.into_inner(), b"ok");
+    }
+}
@@ -0,0 +1,365 @@
+use crate::stress::{Command, Comparator};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How a program finished.
+#[derive(Clone, Debug)]
+pub struct Execution {
+    pub stdout: String,
+    pub stderr: String,
+    /// `None` if killed by a signal, including the time limit.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub time: Duration,
+    /// Peak resident set size in KiB, if measured.
+    pub memory_kib: Option<u64>,
+}
+
+impl Execution {
+    pub fn success(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+/// Runs `command` with `input` as stdin, killing it after `time_limit`.
+pub fn execute(command: &Command, input: &[u8], time_limit: Duration) -> io::Result<Execution> {
+    let start = Instant::now();
+    let mut cmd = process::Command::new(&command.program);
+    cmd.args(&command.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // in its own process group, so that its descendants are killed together on timeout
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let mut child = cmd.spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_vec();
+    let writer = thread::spawn(move || {
+        let _ = stdin.write_all(&input);
+    });
+    let read_all = |mut r: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut buf = vec![];
+            let _ = r.read_to_end(&mut buf);
+            String::from_utf8_lossy(&buf).into_owned()
+        })
+    };
+    let stdout = read_all(Box::new(child.stdout.take().unwrap()));
+    let stderr = read_all(Box::new(child.stderr.take().unwrap()));
+    let (exit_code, timed_out, time, memory_kib) = wait(&mut child, start, time_limit)?;
+    writer.join().unwrap();
+    Ok(Execution {
+        stdout: stdout.join().unwrap(),
+        stderr: stderr.join().unwrap(),
+        exit_code,
+        timed_out,
+        time,
+        memory_kib,
+    })
+}
+
+/// Polls the child by `wait4`, which also reports the peak memory.
+#[cfg(unix)]
+fn wait(
+    child: &mut process::Child,
+    start: Instant,
+    time_limit: Duration,
+) -> io::Result<(Option<i32>, bool, Duration, Option<u64>)> {
+    let pid = child.id() as libc::pid_t;
+    let mut timed_out = false;
+    loop {
+        let mut status = 0;
+        // SAFETY: `rusage` is plain old data, and both pointers are valid during the call.
+        let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+        let flags = if timed_out { 0 } else { libc::WNOHANG };
+        let r = unsafe { libc::wait4(pid, &mut status, flags, &mut usage) };
+        if r < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if r == pid {
+            let time = start.elapsed();
+            let exit_code = libc::WIFEXITED(status).then(|| libc::WEXITSTATUS(status));
+            // `ru_maxrss` is in bytes on macOS, and in KiB elsewhere
+            #[cfg(target_os = "macos")]
+            let memory_kib = usage.ru_maxrss as u64 / 1024;
+            #[cfg(not(target_os = "macos"))]
+            let memory_kib = usage.ru_maxrss as u64;
+            return Ok((exit_code, timed_out, time, Some(memory_kib)));
+        }
+        if start.elapsed() > time_limit {
+            // SAFETY: signals the process group created in `execute`.
+            unsafe { libc::kill(-pid, libc::SIGKILL) };
+            timed_out = true;
+        } else {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn wait(
+    child: &mut process::Child,
+    start: Instant,
+    time_limit: Duration,
+) -> io::Result<(Option<i32>, bool, Duration, Option<u64>)> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status.code(), false, start.elapsed(), None));
+        }
+        if start.elapsed() > time_limit {
+            child.kill()?;
+            child.wait()?;
+            return Ok((None, true, start.elapsed(), None));
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Decides whether an output is correct.
+#[derive(Clone, Debug)]
+pub enum Checker {
+    Compare(Comparator),
+    /// An executable called as `checker <input> <output> <answer>` (the testlib order),
+    /// accepting iff it exits with 0.
+    Custom(Command),
+}
+
+impl Checker {
+    /// `exact`, `tokens`, `float:<eps>`, or otherwise a checker command.
+    pub fn parse(s: &str) -> Self {
+        s.parse::<Comparator>()
+            .map(Checker::Compare)
+            .unwrap_or_else(|_| Checker::Custom(Command::parse(s)))
+    }
+
+    pub fn check(&self, input: &Path, output: &str, answer: &Path) -> io::Result<bool> {
+        match self {
+            Checker::Compare(cmp) => Ok(cmp.matches(&fs::read_to_string(answer)?, output)),
+            Checker::Custom(command) => {
+                let output_file = std::env::temp_dir().join(format!(
+                    "rust_procon_judge_{}_{}.out",
+                    process::id(),
+                    input.file_stem().and_then(|s| s.to_str()).unwrap_or("case")
+                ));
+                fs::write(&output_file, output)?;
+                let status = process::Command::new(&command.program)
+                    .args(&command.args)
+                    .arg(input)
+                    .arg(&output_file)
+                    .arg(answer)
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status();
+                fs::remove_file(&output_file)?;
+                Ok(status?.success())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Verdict {
+    AC,
+    WA,
+    TLE,
+    RE,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Verdict::AC => "AC",
+            Verdict::WA => "WA",
+            Verdict::TLE => "TLE",
+            Verdict::RE => "RE",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CaseResult {
+    pub name: String,
+    pub verdict: Verdict,
+    pub execution: Execution,
+}
+
+/// `*.in` files in `dir` which have the corresponding `*.out`, in the order of names
+/// (the layout of `online-judge-tools`).
+pub fn collect_cases(dir: &Path) -> io::Result<Vec<(String, PathBuf, PathBuf)>> {
+    let mut cases = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "in"))
+        .filter_map(|input| {
+            let answer = input.with_extension("out");
+            let name = input.file_stem()?.to_str()?.to_string();
+            answer.is_file().then_some((name, input, answer))
+        })
+        .collect::<Vec<_>>();
+    cases.sort_by_key(|case| natural_key(&case.0));
+    Ok(cases)
+}
+
+/// Sorts `sample-2` before `sample-10`.
+fn natural_key(s: &str) -> Vec<(String, u64)> {
+    let mut res = vec![];
+    let mut chars = s.chars().peekable();
+    while chars.peek().is_some() {
+        let mut text = String::new();
+        while let Some(&c) = chars.peek().filter(|c| !c.is_ascii_digit()) {
+            text.push(c);
+            chars.next();
+        }
+        let mut num = 0u64;
+        while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+            num = num.saturating_mul(10).saturating_add(d as u64);
+            chars.next();
+        }
+        res.push((text, num));
+    }
+    res
+}
+
+/// Runs every case of `dir`.
+pub fn judge(
+    command: &Command,
+    dir: &Path,
+    checker: &Checker,
+    time_limit: Duration,
+) -> io::Result<Vec<CaseResult>> {
+    collect_cases(dir)?
+        .into_iter()
+        .map(|(name, input, answer)| {
+            let execution = execute(command, &fs::read(&input)?, time_limit)?;
+            let verdict = if execution.timed_out {
+                Verdict::TLE
+            } else if !execution.success() {
+                Verdict::RE
+            } else if checker.check(&input, &execution.stdout, &answer)? {
+                Verdict::AC
+            } else {
+                Verdict::WA
+            };
+            Ok(CaseResult {
+                name,
+                verdict,
+                execution,
+            })
+        })
+        .collect()
+}
+
+/// The result table with a summary line.
+pub fn summary(results: &[CaseResult]) -> String {
+    let width = results
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let mut res = format!(
+        "{:<width$}  {:<7}  {:>8}  {:>10}\n",
+        "case", "verdict", "time", "memory"
+    );
+    for r in results {
+        let memory = r
+            .execution
+            .memory_kib
+            .map_or("-".to_string(), |m| format!("{} KiB", m));
+        res += &format!(
+            "{:<width$}  {:<7}  {:>5} ms  {:>10}\n",
+            r.name,
+            r.verdict,
+            r.execution.time.as_millis(),
+            memory
+        );
+    }
+    let count = |v| results.iter().filter(|r| r.verdict == v).count();
+    res += &format!(
+        "AC {} / {}  (WA {}, TLE {}, RE {})\n",
+        count(Verdict::AC),
+        results.len(),
+        count(Verdict::WA),
+        count(Verdict::TLE),
+        count(Verdict::RE)
+    );
+    res
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn judge_verdicts() {
+        let dir = std::env::temp_dir().join(format!("rust_procon_judge_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, input, answer) in [
+            ("sample-1", "3\n", "3\n"),
+            ("sample-2", "sleep\n", "sleep\n"),
+            ("sample-10", "fail\n", ""),
+            ("sample-3", "1.0\n", "1.0000001\n"),
+        ] {
+            fs::write(dir.join(format!("{}.in", name)), input).unwrap();
+            fs::write(dir.join(format!("{}.out", name)), answer).unwrap();
+        }
+        fs::write(dir.join("no-answer.in"), "").unwrap();
+        let script = dir.join("solution.sh");
+        fs::write(
+            &script,
+            "#!/bin/sh\nread x\ncase $x in sleep) sleep 5;; fail) exit 1;; esac\necho $x\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let command = Command::new(script.to_str().unwrap());
+
+        let results = judge(
+            &command,
+            &dir,
+            &Checker::parse("tokens"),
+            Duration::from_millis(300),
+        )
+        .unwrap();
+        let names = results.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["sample-1", "sample-2", "sample-3", "sample-10"]);
+        let verdicts = results.iter().map(|r| r.verdict).collect::<Vec<_>>();
+        assert_eq!(
+            verdicts,
+            [Verdict::AC, Verdict::TLE, Verdict::WA, Verdict::RE]
+        );
+        assert!(results[0].execution.memory_kib.unwrap() > 0);
+        assert!(results[1].execution.time < Duration::from_secs(2));
+        assert!(summary(&results).ends_with("AC 1 / 4  (WA 1, TLE 1, RE 1)\n"));
+
+        let float = judge(
+            &command,
+            &dir,
+            &Checker::parse("float:1e-6"),
+            Duration::from_millis(300),
+        )
+        .unwrap();
+        assert_eq!(float[2].verdict, Verdict::AC);
+
+        // accepts iff the output equals the input
+        let checker = dir.join("checker.sh");
+        fs::write(&checker, "#!/bin/sh\ncmp -s \"$1\" \"$2\"\n").unwrap();
+        fs::set_permissions(&checker, fs::Permissions::from_mode(0o755)).unwrap();
+        let custom = judge(
+            &command,
+            &dir,
+            &Checker::parse(checker.to_str().unwrap()),
+            Duration::from_millis(300),
+        )
+        .unwrap();
+        assert_eq!(custom[2].verdict, Verdict::AC);
+        assert_eq!(custom[0].verdict, Verdict::AC);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -2,4 +2,14 @@
 /// whose binary operation(op) is closed.
 pub mod algebra;
 pub mod algo;
+/// Random test data reproducible from a seed: trees, graphs, sequences, strings and range queries.
+pub mod gen;
+/// Channels for interactive problems, an in-process mock jury and a process pair runner.
+pub mod interactive;
+/// Fast input scanning and buffered output.
+pub mod io;
+/// Running a solution over local sample cases with time and memory measurement.
+pub mod judge;
+/// Randomized comparison of a solution against a reference.
+pub mod stress;
 pub mod structure;
@@ -0,0 +1,425 @@
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Stdio};
+use std::{fs, thread};
+
+/// Makes a test input from a seed.
+pub trait Generator {
+    fn generate(&mut self, seed: u64) -> Result<String, String>;
+}
+
+/// A closure drawing from `Pcg64::seed_from_u64(seed)`.
+impl<F: FnMut(&mut Pcg64) -> String> Generator for F {
+    fn generate(&mut self, seed: u64) -> Result<String, String> {
+        Ok(self(&mut Pcg64::seed_from_u64(seed)))
+    }
+}
+
+/// Answers an input. `Err` stands for a runtime error.
+pub trait Solver {
+    fn solve(&mut self, input: &str) -> Result<String, String>;
+}
+
+impl<F: FnMut(&str) -> String> Solver for F {
+    fn solve(&mut self, input: &str) -> Result<String, String> {
+        Ok(self(input))
+    }
+}
+
+/// An executable, reading the input from stdin and writing the output to stdout.
+///
+/// As a [Generator], the seed is passed as the last argument.
+#[derive(Clone, Debug)]
+pub struct Command {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl Command {
+    pub fn new<S: Into<String>>(program: S) -> Self {
+        Self {
+            program: program.into(),
+            args: vec![],
+        }
+    }
+
+    /// Splits a command line by whitespace.
+    pub fn parse(line: &str) -> Self {
+        let mut words = line.split_whitespace().map(String::from);
+        Self {
+            program: words.next().expect("empty command"),
+            args: words.collect(),
+        }
+    }
+
+    pub fn arg<S: Into<String>>(mut self, arg: S) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Runs with `input` as stdin, and returns stdout if it exits successfully.
+    pub fn run(&self, args: &[String], input: &str) -> Result<String, String> {
+        let mut child = process::Command::new(&self.program)
+            .args(self.args.iter().chain(args))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("{}: {}", self.program, e))?;
+        let mut stdin = child.stdin.take().unwrap();
+        let input = input.to_string();
+        // written from another thread, so that a full stdout pipe does not block us
+        let writer = thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        writer.join().unwrap();
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(format!(
+                "{} exited with {}\n{}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            ))
+        }
+    }
+}
+
+impl Generator for Command {
+    fn generate(&mut self, seed: u64) -> Result<String, String> {
+        self.run(&[seed.to_string()], "")
+    }
+}
+
+impl Solver for Command {
+    fn solve(&mut self, input: &str) -> Result<String, String> {
+        self.run(&[], input)
+    }
+}
+
+/// How to compare the expected output and the actual one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparator {
+    /// Byte-wise, except for trailing whitespace at the end.
+    Exact,
+    /// Whitespace separated tokens.
+    Tokens,
+    /// Tokens, where numbers may differ within the absolute or relative error.
+    Float(f64),
+}
+
+impl Comparator {
+    pub fn matches(&self, expected: &str, actual: &str) -> bool {
+        match *self {
+            Comparator::Exact => expected.trim_end() == actual.trim_end(),
+            Comparator::Tokens => expected.split_whitespace().eq(actual.split_whitespace()),
+            Comparator::Float(eps) => {
+                let (e, a) = (
+                    expected.split_whitespace().collect::<Vec<_>>(),
+                    actual.split_whitespace().collect::<Vec<_>>(),
+                );
+                e.len() == a.len()
+                    && e.iter()
+                        .zip(&a)
+                        .all(|(x, y)| match (x.parse::<f64>(), y.parse::<f64>()) {
+                            (Ok(x), Ok(y)) => {
+                                (x - y).abs() <= eps || (x - y).abs() <= eps * x.abs()
+                            }
+                            _ => x == y,
+                        })
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Comparator {
+    type Err = String;
+
+    /// `exact`, `tokens` or `float:<eps>`.
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "exact" => Ok(Comparator::Exact),
+            "tokens" => Ok(Comparator::Tokens),
+            _ => s
+                .strip_prefix("float:")
+                .and_then(|eps| eps.parse().ok())
+                .map(Comparator::Float)
+                .ok_or_else(|| format!("unknown comparator: {}", s)),
+        }
+    }
+}
+
+/// The first failing case. `expected` or `actual` holds the error message on a runtime error.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub seed: u64,
+    pub input: String,
+    pub expected: Result<String, String>,
+    pub actual: Result<String, String>,
+    /// The I/O error while saving the case, if any.
+    pub save_error: Option<String>,
+}
+
+/// Compares `reference` and `candidate` on generated inputs, with seeds `seed, seed + 1, ...`
+/// (wrapping around at `u64::MAX`).
+pub struct Stress {
+    seed: u64,
+    iterations: u64,
+    comparator: Comparator,
+    save_dir: Option<PathBuf>,
+}
+
+impl Default for Stress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stress {
+    pub fn new() -> Self {
+        Self {
+            seed: 0,
+            iterations: 1000,
+            comparator: Comparator::Tokens,
+            save_dir: None,
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn comparator(mut self, comparator: Comparator) -> Self {
+        self.comparator = comparator;
+        self
+    }
+
+    /// Saves the failing case into `<dir>/seed_<seed>.{in,expected,actual}`.
+    pub fn save_to<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.save_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Returns the number of passed cases, or the first failure.
+    /// An error of the generator is a failure with empty `input` and the error in `expected`.
+    pub fn run<G, R, C>(
+        &self,
+        generator: &mut G,
+        reference: &mut R,
+        candidate: &mut C,
+    ) -> Result<u64, Failure>
+    where
+        G: Generator + ?Sized,
+        R: Solver + ?Sized,
+        C: Solver + ?Sized,
+    {
+        for seed in (0..self.iterations).map(|i| self.seed.wrapping_add(i)) {
+            let input = match generator.generate(seed) {
+                Ok(input) => input,
+                Err(e) => {
+                    return Err(Failure {
+                        seed,
+                        input: String::new(),
+                        expected: Err(e),
+                        actual: Err(String::new()),
+                        save_error: None,
+                    })
+                }
+            };
+            let expected = reference.solve(&input);
+            let actual = candidate.solve(&input);
+            let ok = match (&expected, &actual) {
+                (Ok(e), Ok(a)) => self.comparator.matches(e, a),
+                _ => false,
+            };
+            if !ok {
+                let mut failure = Failure {
+                    seed,
+                    input,
+                    expected,
+                    actual,
+                    save_error: None,
+                };
+                if let Some(dir) = &self.save_dir {
+                    failure.save_error = save(dir, &failure).err().map(|e| e.to_string());
+                }
+                return Err(failure);
+            }
+        }
+        Ok(self.iterations)
+    }
+}
+
+fn save(dir: &Path, failure: &Failure) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let base = dir.join(format!("seed_{}", failure.seed));
+    let text = |r: &Result<String, String>| match r {
+        Ok(s) => s.clone(),
+        Err(e) => format!("runtime error: {}", e),
+    };
+    fs::write(base.with_extension("in"), &failure.input)?;
+    fs::write(base.with_extension("expected"), text(&failure.expected))?;
+    fs::write(base.with_extension("actual"), text(&failure.actual))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    fn generate(rng: &mut Pcg64) -> String {
+        let n = rng.gen_range(1..10);
+        let a = (0..n)
+            .map(|_| rng.gen_range(-5..=5).to_string())
+            .collect::<Vec<_>>();
+        format!("{}\n{}\n", n, a.join(" "))
+    }
+
+    fn max_subarray_naive(input: &str) -> String {
+        let a = input
+            .split_whitespace()
+            .skip(1)
+            .map(|x| x.parse::<i64>().unwrap())
+            .collect::<Vec<_>>();
+        let mut best = i64::MIN;
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                best = best.max(a[l..r].iter().sum());
+            }
+        }
+        best.to_string()
+    }
+
+    /// Kadane's algorithm, or its wrong version which allows the empty subarray.
+    fn max_subarray(allow_empty: bool) -> impl FnMut(&str) -> String {
+        move |input| {
+            let a = input
+                .split_whitespace()
+                .skip(1)
+                .map(|x| x.parse::<i64>().unwrap());
+            let (mut best, mut cur) = (if allow_empty { 0 } else { i64::MIN }, 0);
+            for x in a {
+                cur = (cur + x).max(x);
+                best = best.max(cur);
+            }
+            format!("{}\n", best)
+        }
+    }
+
+    #[test]
+    fn stress_finds_failure() {
+        let stress = Stress::new().seed(100).iterations(500);
+        let passed = stress.run(
+            &mut generate,
+            &mut max_subarray_naive,
+            &mut max_subarray(false),
+        );
+        assert_eq!(passed, Ok(500));
+
+        let dir = std::env::temp_dir().join(format!("rust_procon_stress_{}", std::process::id()));
+        let failure = stress
+            .save_to(&dir)
+            .run(
+                &mut generate,
+                &mut max_subarray_naive,
+                &mut max_subarray(true),
+            )
+            .unwrap_err();
+        // reproducible from the seed only
+        assert_eq!(
+            generate(&mut Pcg64::seed_from_u64(failure.seed)),
+            failure.input
+        );
+        assert!(failure
+            .input
+            .split_whitespace()
+            .skip(1)
+            .all(|x| x.starts_with('-')));
+        assert_eq!(failure.actual, Ok("0\n".to_string()));
+        assert_eq!(failure.save_error, None);
+        let saved = fs::read_to_string(dir.join(format!("seed_{}.in", failure.seed))).unwrap();
+        assert_eq!(saved, failure.input);
+
+        // a file in place of the directory: the outputs are kept, and the error is reported aside
+        let file = dir.join("file");
+        fs::write(&file, "").unwrap();
+        let failure = Stress::new()
+            .save_to(&file)
+            .run(
+                &mut generate,
+                &mut max_subarray_naive,
+                &mut max_subarray(true),
+            )
+            .unwrap_err();
+        assert!(failure.expected.is_ok());
+        assert!(failure.save_error.is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn stress_seed_wraps() {
+        struct Seeds(Vec<u64>);
+        impl Generator for Seeds {
+            fn generate(&mut self, seed: u64) -> Result<String, String> {
+                self.0.push(seed);
+                Ok(String::new())
+            }
+        }
+        let mut seeds = Seeds(vec![]);
+        let echo = |s: &str| s.to_string();
+        let passed = Stress::new().seed(u64::MAX - 1).iterations(3).run(
+            &mut seeds,
+            &mut echo.clone(),
+            &mut echo.clone(),
+        );
+        assert_eq!(passed, Ok(3));
+        assert_eq!(seeds.0, vec![u64::MAX - 1, u64::MAX, 0]);
+    }
+
+    #[test]
+    fn stress_comparators() {
+        assert!(Comparator::Exact.matches("1 2\n", "1 2"));
+        assert!(!Comparator::Exact.matches("1 2", "1  2"));
+        assert!(Comparator::Tokens.matches("1 2", "1\n  2\n"));
+        assert!(!Comparator::Tokens.matches("1 2", "1 2 3"));
+        let float = "float:1e-6".parse::<Comparator>().unwrap();
+        assert!(float.matches("1.0 abc 1000000", "1.0000001 abc 1000000.5"));
+        assert!(!float.matches("1.0", "1.001"));
+        assert!(!float.matches("abc", "abd"));
+        assert!("foo".parse::<Comparator>().is_err());
+    }
+
+    #[test]
+    fn stress_subprocess() {
+        let mut cat = Command::new("cat");
+        let stress = Stress::new().iterations(20).comparator(Comparator::Exact);
+        assert_eq!(
+            stress.run(&mut generate, &mut |s: &str| s.to_string(), &mut cat),
+            Ok(20)
+        );
+
+        let failure = stress
+            .run(
+                &mut generate,
+                &mut |s: &str| s.to_string(),
+                &mut Command::new("false"),
+            )
+            .unwrap_err();
+        assert_eq!(failure.seed, 0);
+        assert!(failure.actual.is_err());
+
+        let mut generator = Command::parse("echo 1 2");
+        assert_eq!(generator.generate(5), Ok("1 2 5\n".to_string()));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::algebra::{Commutativity, Group, Monoid};
+use crate::algebra::{Group, Monoid};
 use crate::structure::ranged::{LeftFixedFold, RangeFold};
 use std::marker::PhantomData;
 use std::ops::Range;
@@ -36,12 +36,9 @@ impl<E: Clone, T: Monoid<E>> LeftFixedFold<E, T> for AccumulativeArray<E, T> {
     }
 }
 
-impl<E, T> RangeFold<E, T> for AccumulativeArray<E, T>
-where
-    T: Group<E> + Commutativity<E>,
-{
+impl<E, T: Group<E>> RangeFold<E, T> for AccumulativeArray<E, T> {
     fn fold_in(&mut self, range: Range<usize>) -> E {
-        T::op(&self.data[range.end], &T::inv(&self.data[range.start]))
+        T::op(&T::inv(&self.data[range.start]), &self.data[range.end])
     }
 }
 
@@ -0,0 +1,136 @@
+use crate::algebra::{Commutativity, Idempotence, Monoid};
+use crate::structure::ranged::sparse_table::SparseTable;
+use crate::structure::ranged::RangeFold;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// Splits the array into blocks of length `b ~ logN`, keeping the prefix and suffix folds inside each block
+/// and a [SparseTable] over the N/b block folds, all of which take O(N) to build.
+///
+/// A range crossing a block boundary is folded in O(1). A range strictly inside one block is scanned in O(logN).
+#[derive(Debug, Clone)]
+pub struct BlockSparseTable<E, T> {
+    alg: PhantomData<T>,
+    block: usize,
+    data: Vec<E>,
+    /// `prefix[i]` is the fold from the start of the block of `i` to `i`, inclusive.
+    prefix: Vec<E>,
+    /// `suffix[i]` is the fold from `i` to the end of its block.
+    suffix: Vec<E>,
+    blocks: SparseTable<E, T>,
+}
+
+impl<E, T> BlockSparseTable<E, T> {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> From<Vec<E>> for BlockSparseTable<E, T> {
+    fn from(data: Vec<E>) -> Self {
+        let block = data.len().max(2).ilog2() as usize;
+        let mut prefix = data.clone();
+        let mut suffix = data.clone();
+        for (p, s) in prefix.chunks_mut(block).zip(suffix.chunks_mut(block)) {
+            for i in 1..p.len() {
+                p[i] = T::op(&p[i - 1], &p[i]);
+            }
+            for i in (0..s.len() - 1).rev() {
+                s[i] = T::op(&s[i], &s[i + 1]);
+            }
+        }
+        let blocks = suffix.iter().step_by(block).cloned().collect();
+        Self {
+            alg: Default::default(),
+            block,
+            data,
+            prefix,
+            suffix,
+            blocks,
+        }
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> From<&[E]> for BlockSparseTable<E, T> {
+    fn from(a: &[E]) -> Self {
+        Self::from(a.to_vec())
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> FromIterator<E> for BlockSparseTable<E, T> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<E: Clone, T> RangeFold<E, T> for BlockSparseTable<E, T>
+where
+    T: Monoid<E> + Idempotence<E> + Commutativity<E>,
+{
+    fn fold_in(&mut self, range: Range<usize>) -> E {
+        assert!(range.start <= range.end && range.end <= self.len());
+        if range.is_empty() {
+            return T::id();
+        }
+        let (l, r) = (range.start, range.end - 1);
+        let (bl, br) = (l / self.block, r / self.block);
+        if bl < br {
+            let middle = self.blocks.fold_in(bl + 1..br);
+            T::op(&T::op(&self.suffix[l], &middle), &self.prefix[r])
+        } else if l % self.block == 0 {
+            self.prefix[r].clone()
+        } else if (r + 1) % self.block == 0 || r + 1 == self.len() {
+            self.suffix[l].clone()
+        } else {
+            // strictly inside a block, so shorter than `b`
+            self.data[range]
+                .iter()
+                .fold(T::id(), |acc, x| T::op(&acc, x))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BlockSparseTable;
+    use crate::algebra::typical::{GcdMonoid, MaxMonoid};
+    use crate::structure::ranged::naive_vec::NaiveVec;
+    use crate::structure::ranged::RangeFold;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn block_sparse_random() {
+        let mut rng = Pcg64::seed_from_u64(48);
+        for n in (0..70).chain([300]) {
+            let x = (0..n)
+                .map(|_| rng.gen_range(-1000..1000))
+                .collect::<Vec<i64>>();
+            let mut bt = BlockSparseTable::<i64, MaxMonoid>::from(x.clone());
+            let mut nv = NaiveVec::<i64, MaxMonoid>::from(x);
+            assert_eq!(bt.len(), n);
+            for i in 0..=n {
+                for j in i..=n {
+                    assert_eq!(bt.fold_in(i..j), nv.fold_in(i..j), "{:?}", i..j);
+                }
+            }
+        }
+        let x = (0..1000)
+            .map(|_| rng.gen_range(1..1000) * 10)
+            .collect::<Vec<u64>>();
+        let mut bt = x
+            .iter()
+            .cloned()
+            .collect::<BlockSparseTable<u64, GcdMonoid>>();
+        let mut nv = NaiveVec::<u64, GcdMonoid>::from(x);
+        for _ in 0..1000 {
+            let (a, b) = (rng.gen_range(0..=1000), rng.gen_range(0..=1000));
+            let r = a.min(b)..a.max(b);
+            assert_eq!(bt.fold_in(r.clone()), nv.fold_in(r));
+        }
+    }
+}
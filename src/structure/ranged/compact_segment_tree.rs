@@ -0,0 +1,202 @@
+use crate::algebra::Monoid;
+use crate::structure::ranged::{LeftFixedFold, PointAssign, RangeFold};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// Bottom-up segment tree of exactly `2N` nodes: the leaves are `data[N..2N]`, and `data[i] = data[2i] op data[2i+1]`.
+///
+/// Nodes may span two levels when `N` is not a power of two, but the folds keep the order of the operands.
+#[derive(Debug, Clone)]
+pub struct CompactSegmentTree<E, T> {
+    alg: PhantomData<T>,
+    data: Vec<E>,
+}
+
+impl<E, T> CompactSegmentTree<E, T> {
+    pub fn len(&self) -> usize {
+        self.data.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> &E {
+        assert!(index < self.len());
+        &self.data[self.len() + index]
+    }
+
+    /// The leaves, i.e. the current array.
+    pub fn as_slice(&self) -> &[E] {
+        &self.data[self.len()..]
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> CompactSegmentTree<E, T> {
+    /// `n` identities.
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![T::id(); n])
+    }
+
+    #[inline]
+    fn update(&mut self, i: usize) {
+        self.data[i] = T::op(&self.data[2 * i], &self.data[2 * i + 1]);
+    }
+
+    /// Assigns all of `(index, elem)` in order, then recomputes each affected node once:
+    /// O(min(N, K logN)) besides sorting.
+    pub fn set_many<I: IntoIterator<Item = (usize, E)>>(&mut self, items: I) {
+        let n = self.len();
+        let mut nodes = items
+            .into_iter()
+            .map(|(i, x)| {
+                self.data[n + i] = x;
+                n + i
+            })
+            .collect::<Vec<_>>();
+        if nodes.len() * (n.max(1).ilog2() as usize + 1) >= n {
+            (1..n).rev().for_each(|i| self.update(i));
+            return;
+        }
+        // parents are processed in decreasing order, so that children are done before them
+        while !nodes.is_empty() {
+            nodes.iter_mut().for_each(|i| *i /= 2);
+            nodes.retain(|&i| i > 0);
+            nodes.sort_unstable_by(|a, b| b.cmp(a));
+            nodes.dedup();
+            nodes.iter().for_each(|&i| self.update(i));
+        }
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> PointAssign<E, T> for CompactSegmentTree<E, T> {
+    fn set_at(&mut self, elem: E, index: usize) {
+        let mut index = index + self.len();
+        self.data[index] = elem;
+        while index > 1 {
+            index /= 2;
+            self.update(index);
+        }
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> From<Vec<E>> for CompactSegmentTree<E, T> {
+    fn from(a: Vec<E>) -> Self {
+        let n = a.len();
+        let mut data = Vec::with_capacity(2 * n);
+        data.resize(n, T::id());
+        data.extend(a);
+        let mut res = Self {
+            alg: Default::default(),
+            data,
+        };
+        (1..n).rev().for_each(|i| res.update(i));
+        res
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> From<&[E]> for CompactSegmentTree<E, T> {
+    fn from(a: &[E]) -> Self {
+        Self::from(a.to_vec())
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> FromIterator<E> for CompactSegmentTree<E, T> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> LeftFixedFold<E, T> for CompactSegmentTree<E, T> {
+    fn fold_to(&mut self, r: usize) -> E {
+        self.fold_in(0..r)
+    }
+}
+
+impl<E: Clone, T: Monoid<E>> RangeFold<E, T> for CompactSegmentTree<E, T> {
+    fn fold_in(&mut self, range: Range<usize>) -> E {
+        assert!(range.start <= range.end && range.end <= self.len());
+        let mut res_left = T::id();
+        let mut res_right = T::id();
+        let mut l = range.start + self.len();
+        let mut r = range.end + self.len();
+        while l < r {
+            if l % 2 == 1 {
+                res_left = T::op(&res_left, &self.data[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                res_right = T::op(&self.data[r], &res_right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        T::op(&res_left, &res_right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CompactSegmentTree;
+    use crate::algebra::typical::{AdditiveStruct, StringChain};
+    use crate::structure::ranged::naive_vec::NaiveVec;
+    use crate::structure::ranged::{LeftFixedFold, PointAssign, RangeFold};
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    fn letter(rng: &mut Pcg64) -> String {
+        ((b'a' + rng.gen_range(0..26)) as char).to_string()
+    }
+
+    #[test]
+    fn compact_seg_string_chain() {
+        let mut rng = Pcg64::seed_from_u64(49);
+        for n in 0..40 {
+            let x = (0..n).map(|_| letter(&mut rng)).collect::<Vec<_>>();
+            let mut st = x
+                .iter()
+                .cloned()
+                .collect::<CompactSegmentTree<_, StringChain>>();
+            let mut nv = NaiveVec::<String, StringChain>::from(x.clone());
+            assert_eq!((st.len(), st.is_empty()), (n, n == 0));
+            for _ in 0..3 {
+                if n > 0 {
+                    let (i, s) = (rng.gen_range(0..n), letter(&mut rng));
+                    st.set_at(s.clone(), i);
+                    nv.set_at(s, i);
+                }
+                for i in 0..=n {
+                    assert_eq!(st.fold_to(i), nv.fold_to(i));
+                    for j in i..=n {
+                        assert_eq!(st.fold_in(i..j), nv.fold_in(i..j), "{:?}", i..j);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compact_seg_set_many() {
+        let mut rng = Pcg64::seed_from_u64(49);
+        for n in [1, 2, 7, 100, 1000] {
+            let mut st = CompactSegmentTree::<i64, AdditiveStruct>::new(n);
+            let mut a = vec![0; n];
+            for _ in 0..20 {
+                let k = rng.gen_range(0..=n / 2 + 1);
+                let items = (0..k)
+                    .map(|_| (rng.gen_range(0..n), rng.gen_range(-100..100)))
+                    .collect::<Vec<_>>();
+                items.iter().for_each(|&(i, x)| a[i] = x);
+                st.set_many(items);
+                assert_eq!(st.as_slice(), a.as_slice());
+                let i = rng.gen_range(0..n);
+                assert_eq!(*st.get(i), a[i]);
+                let (l, r) = (rng.gen_range(0..=n), rng.gen_range(0..=n));
+                let (l, r) = (l.min(r), l.max(r));
+                assert_eq!(st.fold_in(l..r), a[l..r].iter().sum::<i64>());
+                assert_eq!(st.fold_in(0..n), a.iter().sum::<i64>());
+            }
+        }
+    }
+}
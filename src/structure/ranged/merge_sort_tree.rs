@@ -0,0 +1,275 @@
+use crate::algebra::{Commutativity, Monoid};
+use crate::structure::ranged::accumulative_array::AccumulativeArray;
+use crate::structure::ranged::LeftFixedFold;
+use std::ops::Range;
+
+/// Merges two lists sorted by `less`, taking from `a` first on ties.
+fn merge<X: Clone>(a: &[X], b: &[X], less: impl Fn(&X, &X) -> bool) -> Vec<X> {
+    let mut res = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if less(&b[j], &a[i]) {
+            res.push(b[j].clone());
+            j += 1;
+        } else {
+            res.push(a[i].clone());
+            i += 1;
+        }
+    }
+    res.extend_from_slice(&a[i..]);
+    res.extend_from_slice(&b[j..]);
+    res
+}
+
+/// Bottom-up segment tree of `2N` nodes, each keeping the keys of its segment in sorted order,
+/// and optionally the prefix folds of the values in that order.
+#[derive(Debug, Clone)]
+pub struct MergeSortTree<K, E, T> {
+    keys: Vec<Vec<K>>,
+    folds: Option<Vec<AccumulativeArray<E, T>>>,
+}
+
+impl<K: Ord + Clone, E, T> MergeSortTree<K, E, T> {
+    /// Only for counting. `E` and `T` are unused.
+    pub fn new(a: Vec<K>) -> Self {
+        let n = a.len();
+        let mut keys = vec![vec![]; n];
+        keys.extend(a.into_iter().map(|k| vec![k]));
+        for i in (1..n).rev() {
+            keys[i] = merge(&keys[2 * i], &keys[2 * i + 1], |x, y| x < y);
+        }
+        Self { keys, folds: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The O(logN) nodes covering `range`, each with the number of its keys less than `x`.
+    fn nodes(&self, range: Range<usize>, x: &K) -> Vec<(usize, usize)> {
+        assert!(range.start <= range.end && range.end <= self.len());
+        let count = |i: usize| (i, self.keys[i].partition_point(|k| k < x));
+        let mut res = vec![];
+        let mut l = range.start + self.len();
+        let mut r = range.end + self.len();
+        while l < r {
+            if l % 2 == 1 {
+                res.push(count(l));
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                res.push(count(r));
+            }
+            l /= 2;
+            r /= 2;
+        }
+        res
+    }
+
+    /// The number of `i` in `range` with `key_i < x`: O(log^2 N).
+    pub fn count_less(&self, range: Range<usize>, x: &K) -> usize {
+        self.nodes(range, x).into_iter().map(|(_, c)| c).sum()
+    }
+}
+
+impl<K: Ord + Clone, E: Clone, T: Monoid<E>> MergeSortTree<K, E, T> {
+    /// Elements of `(key, value)` to fold the values by.
+    pub fn with_values(a: Vec<(K, E)>) -> Self {
+        let n = a.len();
+        let mut nodes = vec![vec![]; n];
+        nodes.extend(a.into_iter().map(|p| vec![p]));
+        for i in (1..n).rev() {
+            nodes[i] = merge(&nodes[2 * i], &nodes[2 * i + 1], |p, q| p.0 < q.0);
+        }
+        let (keys, folds) = nodes
+            .into_iter()
+            .map(|node| {
+                let (k, v): (Vec<K>, Vec<E>) = node.into_iter().unzip();
+                (k, AccumulativeArray::from(v))
+            })
+            .unzip();
+        Self {
+            keys,
+            folds: Some(folds),
+        }
+    }
+}
+
+impl<K: Ord + Clone, E: Clone, T: Monoid<E> + Commutativity<E>> MergeSortTree<K, E, T> {
+    /// The fold of `value_i` over `i` in `range` with `key_i < x`: O(log^2 N).
+    pub fn fold_less(&mut self, range: Range<usize>, x: &K) -> E {
+        let nodes = self.nodes(range, x);
+        let folds = self.folds.as_mut().expect("built without values");
+        nodes
+            .into_iter()
+            .fold(T::id(), |acc, (v, c)| T::op(&acc, &folds[v].fold_to(c)))
+    }
+}
+
+/// Top-down segment tree with fractional cascading: only the root keeps the sorted keys,
+/// and each node records how many of its first `i` elements in sorted order come from the left child,
+/// so that a single binary search at the root locates `x` in every node.
+#[derive(Debug, Clone)]
+pub struct CascadingMergeSortTree<K, E, T> {
+    len: usize,
+    size: usize,
+    root: Vec<K>,
+    /// `to_left[v][i]` for internal nodes `v < size` and `i` in `0..=|v|`.
+    to_left: Vec<Vec<usize>>,
+    folds: Option<Vec<AccumulativeArray<E, T>>>,
+}
+
+impl<K: Ord + Clone, E, T> CascadingMergeSortTree<K, E, T> {
+    /// Calls `on_node(v, order)` for every node, `order` being the original indices sorted by key,
+    /// and returns the tree without folds.
+    fn build(keys: Vec<K>, mut on_node: impl FnMut(usize, &[usize])) -> Self {
+        let len = keys.len();
+        let size = len.next_power_of_two();
+        let mut level = (0..size)
+            .map(|i| if i < len { vec![i] } else { vec![] })
+            .collect::<Vec<_>>();
+        level
+            .iter()
+            .enumerate()
+            .for_each(|(i, o)| on_node(size + i, o));
+        let mut to_left = vec![vec![]; size];
+        let mut width = size;
+        while width > 1 {
+            width /= 2;
+            let children = std::mem::take(&mut level);
+            for (j, pair) in children.chunks(2).enumerate() {
+                let v = width + j;
+                let order = merge(&pair[0], &pair[1], |&a, &b| keys[a] < keys[b]);
+                let boundary = len.min((j * 2 + 1) * (size / width / 2));
+                let mut t = Vec::with_capacity(order.len() + 1);
+                t.push(0);
+                for &i in &order {
+                    t.push(t[t.len() - 1] + usize::from(i < boundary));
+                }
+                to_left[v] = t;
+                on_node(v, &order);
+                level.push(order);
+            }
+        }
+        let root = level[0].iter().map(|&i| keys[i].clone()).collect();
+        Self {
+            len,
+            size,
+            root,
+            to_left,
+            folds: None,
+        }
+    }
+
+    /// Only for counting. `E` and `T` are unused.
+    pub fn new(keys: Vec<K>) -> Self {
+        Self::build(keys, |_, _| {})
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The O(logN) nodes covering `range`, each with the number of its keys less than `x`.
+    fn nodes(&self, range: Range<usize>, x: &K) -> Vec<(usize, usize)> {
+        assert!(range.start <= range.end && range.end <= self.len);
+        let p = self.root.partition_point(|k| k < x);
+        let mut res = vec![];
+        let mut stack = vec![(1, 0..self.size, p)];
+        while let Some((v, seg, p)) = stack.pop() {
+            if seg.end <= range.start || range.end <= seg.start {
+                continue;
+            }
+            if range.start <= seg.start && seg.end <= range.end {
+                res.push((v, p));
+                continue;
+            }
+            let mid = (seg.start + seg.end) / 2;
+            let left = self.to_left[v][p];
+            stack.push((2 * v, seg.start..mid, left));
+            stack.push((2 * v + 1, mid..seg.end, p - left));
+        }
+        res
+    }
+
+    /// The number of `i` in `range` with `key_i < x`: O(logN).
+    pub fn count_less(&self, range: Range<usize>, x: &K) -> usize {
+        self.nodes(range, x).into_iter().map(|(_, c)| c).sum()
+    }
+}
+
+impl<K: Ord + Clone, E: Clone, T: Monoid<E>> CascadingMergeSortTree<K, E, T> {
+    /// Elements of `(key, value)` to fold the values by.
+    pub fn with_values(a: Vec<(K, E)>) -> Self {
+        let (keys, values): (Vec<K>, Vec<E>) = a.into_iter().unzip();
+        let mut folds = (0..2 * keys.len().next_power_of_two())
+            .map(|_| AccumulativeArray::from(vec![]))
+            .collect::<Vec<_>>();
+        let mut res = Self::build(keys, |v, order| {
+            folds[v] = order
+                .iter()
+                .map(|&i| values[i].clone())
+                .collect::<Vec<_>>()
+                .into();
+        });
+        res.folds = Some(folds);
+        res
+    }
+}
+
+impl<K: Ord + Clone, E: Clone, T: Monoid<E> + Commutativity<E>> CascadingMergeSortTree<K, E, T> {
+    /// The fold of `value_i` over `i` in `range` with `key_i < x`: O(logN).
+    pub fn fold_less(&mut self, range: Range<usize>, x: &K) -> E {
+        let nodes = self.nodes(range, x);
+        let folds = self.folds.as_mut().expect("built without values");
+        nodes
+            .into_iter()
+            .fold(T::id(), |acc, (v, c)| T::op(&acc, &folds[v].fold_to(c)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CascadingMergeSortTree, MergeSortTree};
+    use crate::algebra::typical::AdditiveStruct;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    #[test]
+    fn merge_sort_tree_random() {
+        let mut rng = Pcg64::seed_from_u64(50);
+        for n in (0..40).chain([1000]) {
+            let a = (0..n)
+                .map(|_| (rng.gen_range(0..20), rng.gen_range(-100..100)))
+                .collect::<Vec<(i32, i64)>>();
+            let keys = a.iter().map(|p| p.0).collect::<Vec<_>>();
+            let count = MergeSortTree::<i32, (), ()>::new(keys.clone());
+            let cascading_count = CascadingMergeSortTree::<i32, (), ()>::new(keys);
+            let mut sum = MergeSortTree::<i32, i64, AdditiveStruct>::with_values(a.clone());
+            let mut cascading_sum =
+                CascadingMergeSortTree::<i32, i64, AdditiveStruct>::with_values(a.clone());
+            assert_eq!((count.len(), cascading_count.len()), (n, n));
+            for _ in 0..300 {
+                let (l, r) = (rng.gen_range(0..=n), rng.gen_range(0..=n));
+                let range = l.min(r)..l.max(r);
+                let x = rng.gen_range(-1..22);
+                let below = a[range.clone()].iter().filter(|p| p.0 < x);
+                let expected = (below.clone().count(), below.map(|p| p.1).sum::<i64>());
+                assert_eq!(count.count_less(range.clone(), &x), expected.0);
+                assert_eq!(cascading_count.count_less(range.clone(), &x), expected.0);
+                assert_eq!(sum.count_less(range.clone(), &x), expected.0);
+                assert_eq!(sum.fold_less(range.clone(), &x), expected.1);
+                assert_eq!(cascading_sum.fold_less(range, &x), expected.1);
+            }
+        }
+    }
+}
@@ -6,12 +6,23 @@ use std::ops::Range;
 ///
 /// For [Group], [RangeFold]: O(1) is valid.
 pub mod accumulative_array;
+/// Build: O(N), [RangeFold]: O(1) if the range crosses a block boundary,
+/// O(logN) if it lies strictly inside one block, needs [Idempotence], [Monoid].
+pub mod block_sparse_table;
+/// Build: O(N), [RangeFold]: O(logN), needs [Monoid]. Exactly 2N nodes for any N.
+/// [PointAssign]: O(logN) is valid.
+pub mod compact_segment_tree;
 /// Build: O(N), [LeftFixedFold]: O(logN), needs [Monoid].
 ///
 /// For [Commutativity], [PointOpAssign]: O(logN) is valid.
 ///
 /// For [Group], [RangeFold]: O(logN) is valid.
 pub mod fenwick_tree;
+/// Build: O(NlogN), counting the elements less than a key in a range: O(log^2 N).
+/// Folding their values needs [Commutativity], [Monoid].
+///
+/// With fractional cascading, both are O(logN).
+pub mod merge_sort_tree;
 /// Build: O(N), [RangeFold]: O(N), needs [Magma]. Used for the other structures verification.
 pub mod naive_vec;
 /// Build: O(N), [RangeFold]: O(logN), needs [Monoid].
@@ -29,7 +29,7 @@ impl<E: Clone, T: Monoid<E>> PointAssign<E, T> for SegmentTree<E, T> {
 
 impl<E: Clone, T: Monoid<E>> From<Vec<E>> for SegmentTree<E, T> {
     fn from(a: Vec<E>) -> Self {
-        let n = 1 << (a.len() as f64).log2().ceil() as usize;
+        let n = a.len().next_power_of_two();
         let mut data = vec![T::id(); n * 2];
         data[n..n + a.len()].clone_from_slice(&a);
         for i in (1..n).rev() {
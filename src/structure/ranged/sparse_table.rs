@@ -3,28 +3,49 @@ use crate::structure::ranged::RangeFold;
 use std::marker::PhantomData;
 use std::ops::Range;
 
+/// Level `k` holds the folds of `a[i..i + 2^k]` for `i in 0..=n - 2^k`, stored level by level in one buffer.
 #[derive(Debug, Clone)]
 pub struct SparseTable<E, T> {
     alg: PhantomData<T>,
-    doubling: Vec<Vec<E>>,
+    len: usize,
+    /// Level `k` is `table[starts[k]..starts[k + 1]]`.
+    starts: Vec<usize>,
+    table: Vec<E>,
+}
+
+impl<E, T> SparseTable<E, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 }
 
 impl<E: Clone, T: Monoid<E>> From<Vec<E>> for SparseTable<E, T> {
-    fn from(a: Vec<E>) -> Self {
+    /// Reuses `a` as level 0, and reserves the other levels at once.
+    fn from(mut a: Vec<E>) -> Self {
         let n = a.len();
-        let t = (n as f32).log2() as usize;
-        let mut doubling = vec![vec![T::id(); t + 1]; n];
-        for i in 0..n {
-            doubling[i][0] = a[i].clone();
+        let levels = if n == 0 { 0 } else { n.ilog2() as usize + 1 };
+        let mut starts = Vec::with_capacity(levels + 1);
+        starts.push(0);
+        for k in 0..levels {
+            starts.push(starts[k] + n + 1 - (1 << k));
         }
-        for j in 0..t {
-            for i in 0..n {
-                doubling[i][j + 1] = T::op(&doubling[i][j], &doubling[(n - 1).min(i + (1 << j))][j]);
+        a.reserve_exact(starts[levels] - n);
+        for k in 1..levels {
+            let (prev, half) = (starts[k - 1], 1 << (k - 1));
+            for i in 0..starts[k + 1] - starts[k] {
+                let x = T::op(&a[prev + i], &a[prev + i + half]);
+                a.push(x);
             }
         }
         Self {
             alg: Default::default(),
-            doubling,
+            len: n,
+            starts,
+            table: a,
         }
     }
 }
@@ -35,33 +56,35 @@ impl<E: Clone, T: Monoid<E>> From<&[E]> for SparseTable<E, T> {
     }
 }
 
+impl<E: Clone, T: Monoid<E>> FromIterator<E> for SparseTable<E, T> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
 impl<E, T> RangeFold<E, T> for SparseTable<E, T>
-    where
-        T: Monoid<E> + Idempotence<E> + Commutativity<E>,
+where
+    T: Monoid<E> + Idempotence<E> + Commutativity<E>,
 {
     fn fold_in(&mut self, range: Range<usize>) -> E {
-        assert!(range.end <= self.doubling.len());
-        let c = range.len();
-        if c == 0 {
-            T::id()
-        } else {
-            let t = (c as f32).log2() as usize;
-            let d = 1 << t;
-
-            T::op(
-                &self.doubling[range.start][t],
-                &self.doubling[range.end - d][t],
-            )
+        assert!(range.start <= range.end && range.end <= self.len);
+        if range.is_empty() {
+            return T::id();
         }
+        let k = range.len().ilog2() as usize;
+        let level = &self.table[self.starts[k]..];
+        T::op(&level[range.start], &level[range.end - (1 << k)])
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::SparseTable;
-    use crate::algebra::typical::MaxMonoid;
+    use crate::algebra::typical::{GcdMonoid, MaxMonoid};
     use crate::structure::ranged::naive_vec::NaiveVec;
     use crate::structure::ranged::RangeFold;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
 
     #[test]
     fn sparse_min() {
@@ -74,4 +97,22 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn sparse_random() {
+        let mut rng = Pcg64::seed_from_u64(48);
+        for n in 0..70 {
+            let x = (0..n)
+                .map(|_| rng.gen_range(1..1000) * 6)
+                .collect::<Vec<u64>>();
+            let mut st = x.iter().cloned().collect::<SparseTable<u64, GcdMonoid>>();
+            let mut nv = NaiveVec::<u64, GcdMonoid>::from(x);
+            assert_eq!(st.len(), n);
+            for i in 0..=n {
+                for j in i..=n {
+                    assert_eq!(st.fold_in(i..j), nv.fold_in(i..j), "{:?}", i..j);
+                }
+            }
+        }
+    }
 }